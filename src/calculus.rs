@@ -1,3 +1,5 @@
+use crate::{MatrixTraits, SquareMatrix};
+
 // Integration through Simpson's rule
 pub fn integrate<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, precision: i32) -> f64 {
     let delta_x = (b - a) / precision as f64;
@@ -21,7 +23,47 @@ pub fn differentiate<F: Fn(f64) -> f64>(f: F, a: f64) -> f64 {
     let delta_x = 0.0000000001;
 
     (f(a + delta_x) - f(a)) / delta_x
-} 
+}
+
+// Central-difference gradient of a scalar function
+pub fn gradient<F: Fn(&[f64]) -> f64>(f: F, x: &[f64]) -> Vec<f64> {
+    let mut shifted = x.to_vec();
+
+    (0..x.len())
+        .map(|i| {
+            let delta_x = f64::EPSILON.cbrt() * f64::max(x[i].abs(), 1.0);
+
+            shifted[i] = x[i] + delta_x;
+            let forward = f(&shifted);
+            shifted[i] = x[i] - delta_x;
+            let backward = f(&shifted);
+            shifted[i] = x[i];
+
+            (forward - backward) / (2.0 * delta_x)
+        })
+        .collect()
+}
+
+// Forward-difference Jacobian of a vector function
+pub fn jacobian<F: Fn(&[f64]) -> Vec<f64>>(f: F, x: &[f64]) -> SquareMatrix {
+    let fx = f(x);
+    let mut jac = SquareMatrix::new(x.len());
+    let mut shifted = x.to_vec();
+
+    for j in 0..x.len() {
+        let delta_x = f64::EPSILON.sqrt() * f64::max(x[j].abs(), 1.0);
+        shifted[j] = x[j] + delta_x;
+        let f_shifted = f(&shifted);
+
+        for i in 0..fx.len() {
+            jac.set(i + 1, j + 1, (f_shifted[i] - fx[i]) / delta_x);
+        }
+
+        shifted[j] = x[j];
+    }
+
+    jac
+}
 
 #[cfg(test)]
 mod tests {
@@ -59,4 +101,30 @@ mod tests {
         let parabula_derivative_3 = (parabula_derivative_3 * 10000.0).round() / 10000.0;
         assert_eq!(parabula_derivative_3, -5.0);
     }
+
+    #[test]
+    fn gradient_test() {
+        let paraboloid = |x: &[f64]| -> f64 { x[0] * x[0] + 3.0 * x[0] * x[1] - x[1] };
+
+        let grad = gradient(paraboloid, &[1.0, 2.0]);
+        let grad: Vec<f64> = grad
+            .iter()
+            .map(|g| (g * 10000.0).round() / 10000.0)
+            .collect();
+
+        assert_eq!(grad, vec![8.0, 2.0]);
+    }
+
+    #[test]
+    fn jacobian_test() {
+        let f = |x: &[f64]| -> Vec<f64> { vec![x[0] * x[1], x[0] + 3.0 * x[1] * x[1]] };
+
+        let jac = jacobian(f, &[2.0, 1.0]);
+        let round = |x: f64| -> f64 { (x * 10000.0).round() / 10000.0 };
+
+        assert_eq!(round(jac.get(1, 1)), 1.0);
+        assert_eq!(round(jac.get(1, 2)), 2.0);
+        assert_eq!(round(jac.get(2, 1)), 1.0);
+        assert_eq!(round(jac.get(2, 2)), 6.0);
+    }
 }
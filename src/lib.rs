@@ -4,7 +4,9 @@ use std::io::{Error, ErrorKind};
 use wasm_bindgen::prelude::*;
 
 mod calculus;
+mod ode;
 mod optimizers;
+mod polynomials;
 mod statistics;
 
 #[wasm_bindgen]
@@ -42,6 +44,16 @@ impl SquareMatrix {
         }
     }
 
+    pub fn identity(s: usize) -> SquareMatrix {
+        let mut mat = SquareMatrix::new(s);
+
+        for x in 1..=s {
+            mat.set(x, x, 1.0);
+        }
+
+        mat
+    }
+
     fn compl(&self, row: usize, col: usize) -> f64 {
         let mut mat: SquareMatrix = SquareMatrix::new(self.size - 1);
 
@@ -116,6 +128,101 @@ impl SquareMatrix {
 
         Ok(inv_mat)
     }
+
+    // Matrix-vector product
+    pub fn mul_vec(&self, v: &[f64]) -> Vec<f64> {
+        (1..=self.size)
+            .map(|x| (1..=self.size).map(|y| self.get(x, y) * v[y - 1]).sum())
+            .collect()
+    }
+
+    // Linear system solution (Gaussian elimination with partial pivoting)
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, Error> {
+        if b.len() != self.size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Mismatched matrix dimensions",
+            ));
+        }
+
+        let n = self.size;
+        let mut a = self.matrix.clone();
+        let mut x = b.to_vec();
+
+        for k in 0..n {
+            // Pick the largest pivot in the current column
+            let mut pivot = k;
+            for i in (k + 1)..n {
+                if a[i * n + k].abs() > a[pivot * n + k].abs() {
+                    pivot = i;
+                }
+            }
+
+            if a[pivot * n + k] == 0.0 {
+                return Err(Error::other(
+                    "Matrix is singular: the system can't be solved",
+                ));
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    a.swap(k * n + j, pivot * n + j);
+                }
+                x.swap(k, pivot);
+            }
+
+            for i in (k + 1)..n {
+                let factor = a[i * n + k] / a[k * n + k];
+                for j in k..n {
+                    a[i * n + j] -= factor * a[k * n + j];
+                }
+                x[i] -= factor * x[k];
+            }
+        }
+
+        // Back substitution
+        for k in (0..n).rev() {
+            for j in (k + 1)..n {
+                x[k] -= a[k * n + j] * x[j];
+            }
+            x[k] /= a[k * n + k];
+        }
+
+        Ok(x)
+    }
+
+    // Cholesky factorization: lower triangular L with L L^T equal to the matrix,
+    // which must be symmetric (only its lower triangle is read) and positive definite
+    pub fn cholesky(&self) -> Result<SquareMatrix, Error> {
+        let n = self.size;
+        let mut l = SquareMatrix::new(n);
+
+        for j in 1..=n {
+            let mut diagonal = self.get(j, j);
+            for k in 1..j {
+                diagonal -= l.get(j, k) * l.get(j, k);
+            }
+
+            if diagonal <= 0.0 || diagonal.is_nan() {
+                return Err(Error::other(
+                    "Matrix is not positive definite: no Cholesky factorization",
+                ));
+            }
+
+            let pivot = diagonal.sqrt();
+            l.set(j, j, pivot);
+
+            for i in (j + 1)..=n {
+                let mut sum = self.get(i, j);
+                for k in 1..j {
+                    sum -= l.get(i, k) * l.get(j, k);
+                }
+                l.set(i, j, sum / pivot);
+            }
+        }
+
+        Ok(l)
+    }
 }
 
 impl MatrixTraits for SquareMatrix {
@@ -406,6 +513,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cholesky_test() {
+        let mut matrix = SquareMatrix::new(3);
+        matrix.set(1, 1, 4.0);
+        matrix.set(1, 2, 12.0);
+        matrix.set(1, 3, -16.0);
+        matrix.set(2, 1, 12.0);
+        matrix.set(2, 2, 37.0);
+        matrix.set(2, 3, -43.0);
+        matrix.set(3, 1, -16.0);
+        matrix.set(3, 2, -43.0);
+        matrix.set(3, 3, 98.0);
+
+        let mut factor = SquareMatrix::new(3);
+        factor.set(1, 1, 2.0);
+        factor.set(2, 1, 6.0);
+        factor.set(2, 2, 1.0);
+        factor.set(3, 1, -8.0);
+        factor.set(3, 2, 5.0);
+        factor.set(3, 3, 3.0);
+
+        assert_eq!(matrix.cholesky().unwrap(), factor);
+
+        // Symmetric but indefinite
+        let mut indefinite = SquareMatrix::new(2);
+        indefinite.set(1, 1, 1.0);
+        indefinite.set(1, 2, 2.0);
+        indefinite.set(2, 1, 2.0);
+        indefinite.set(2, 2, 1.0);
+
+        assert!(indefinite.cholesky().is_err());
+    }
+
     // Matrix tests
 
     #[test]
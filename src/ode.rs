@@ -0,0 +1,583 @@
+use crate::calculus::jacobian;
+use crate::{MatrixTraits, SquareMatrix};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::f64::consts::*;
+use std::io::Error;
+use wasm_bindgen::prelude::*;
+
+/**
+ *  ----------------------------------------------------
+ *  INITIAL VALUE PROBLEMS
+ *  Solvers for y' = f(t, y), y(t0) = y0 on Vec<f64> state
+ *  ----------------------------------------------------
+ */
+
+// Solution of an initial value problem: every accepted step (t, y)
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct OdeSolution {
+    pub t: Vec<f64>,
+    pub y: Vec<Vec<f64>>,
+    // Dormand-Prince continuous extension coefficients, one set per step
+    #[serde(skip)]
+    dense: Vec<[Vec<f64>; 5]>,
+}
+
+impl OdeSolution {
+    fn new(t0: f64, y0: &[f64]) -> OdeSolution {
+        OdeSolution {
+            t: vec![t0],
+            y: vec![y0.to_vec()],
+            dense: Vec::new(),
+        }
+    }
+
+    // Solution at an arbitrary time inside the integration interval.
+    // Uses the dense output when the solver provides one, linear interpolation otherwise.
+    pub fn eval(&self, t: f64) -> Option<Vec<f64>> {
+        let first = self.t[0];
+        let last = self.t[self.t.len() - 1];
+
+        if t < f64::min(first, last) || t > f64::max(first, last) {
+            return None;
+        }
+
+        if self.t.len() == 1 {
+            return Some(self.y[0].clone());
+        }
+
+        // Index of the step containing t
+        let forward = last >= first;
+        let mut step = 0;
+        while step < self.t.len() - 2
+            && ((forward && t > self.t[step + 1]) || (!forward && t < self.t[step + 1]))
+        {
+            step += 1;
+        }
+
+        let theta = (t - self.t[step]) / (self.t[step + 1] - self.t[step]);
+        let theta1 = 1.0 - theta;
+
+        let value = match self.dense.get(step) {
+            Some(r) => (0..r[0].len())
+                .map(|i| {
+                    r[0][i]
+                        + theta
+                            * (r[1][i] + theta1 * (r[2][i] + theta * (r[3][i] + theta1 * r[4][i])))
+                })
+                .collect(),
+            None => (0..self.y[step].len())
+                .map(|i| theta1 * self.y[step][i] + theta * self.y[step + 1][i])
+                .collect(),
+        };
+
+        Some(value)
+    }
+}
+
+// Weighted RMS norm used for step size control
+fn error_norm(err: &[f64], y: &[f64], y_new: &[f64], rtol: f64, atol: f64) -> f64 {
+    let sum: f64 = (0..err.len())
+        .map(|i| {
+            let scale = atol + rtol * f64::max(y[i].abs(), y_new[i].abs());
+            (err[i] / scale).powi(2)
+        })
+        .sum();
+
+    (sum / err.len() as f64).sqrt()
+}
+
+// Heuristic first step (Hairer, Nørsett & Wanner)
+fn initial_step(f0: &[f64], y0: &[f64], span: f64, rtol: f64, atol: f64) -> f64 {
+    let zeros = vec![0.0; y0.len()];
+    let d0 = error_norm(y0, y0, &zeros, rtol, atol);
+    let d1 = error_norm(f0, y0, &zeros, rtol, atol);
+
+    let h = if d0 < 1e-5 || d1 < 1e-5 {
+        1e-6
+    } else {
+        0.01 * d0 / d1
+    };
+
+    f64::min(h, span.abs())
+}
+
+// y + h * sum(c_i * k_i)
+fn combine(y: &[f64], h: f64, terms: &[(f64, &Vec<f64>)]) -> Vec<f64> {
+    let mut out = y.to_vec();
+    for (coeff, k) in terms {
+        if *coeff != 0.0 {
+            for i in 0..out.len() {
+                out[i] += h * coeff * k[i];
+            }
+        }
+    }
+    out
+}
+
+// Classic fixed-step fourth order Runge-Kutta method
+pub fn rk4<F: Fn(f64, &[f64]) -> Vec<f64>>(
+    f: F,
+    t0: f64,
+    t1: f64,
+    y0: &[f64],
+    steps: usize,
+) -> OdeSolution {
+    let mut solution = OdeSolution::new(t0, y0);
+    let h = (t1 - t0) / steps as f64;
+    let mut y = y0.to_vec();
+
+    for n in 0..steps {
+        let t = t0 + n as f64 * h;
+
+        let k1 = f(t, &y);
+        let k2 = f(t + 0.5 * h, &combine(&y, h, &[(0.5, &k1)]));
+        let k3 = f(t + 0.5 * h, &combine(&y, h, &[(0.5, &k2)]));
+        let k4 = f(t + h, &combine(&y, h, &[(1.0, &k3)]));
+
+        y = combine(
+            &y,
+            h,
+            &[
+                (1.0 / 6.0, &k1),
+                (1.0 / 3.0, &k2),
+                (1.0 / 3.0, &k3),
+                (1.0 / 6.0, &k4),
+            ],
+        );
+
+        solution.t.push(t0 + (n + 1) as f64 * h);
+        solution.y.push(y.clone());
+    }
+
+    solution
+}
+
+// Adaptive Dormand-Prince 5(4) method with dense output
+pub fn ode45<F: Fn(f64, &[f64]) -> Vec<f64>>(
+    f: F,
+    t0: f64,
+    t1: f64,
+    y0: &[f64],
+    rtol: f64,
+    atol: f64,
+) -> Result<OdeSolution, Error> {
+    // Butcher tableau
+    const C: [f64; 6] = [0.2, 0.3, 0.8, 8.0 / 9.0, 1.0, 1.0];
+    const A2: [f64; 1] = [0.2];
+    const A3: [f64; 2] = [3.0 / 40.0, 9.0 / 40.0];
+    const A4: [f64; 3] = [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0];
+    const A5: [f64; 4] = [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+    ];
+    const A6: [f64; 5] = [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+    ];
+    const B: [f64; 6] = [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ];
+
+    // Difference between the fifth and the fourth order weights
+    const E: [f64; 7] = [
+        71.0 / 57600.0,
+        0.0,
+        -71.0 / 16695.0,
+        71.0 / 1920.0,
+        -17253.0 / 339200.0,
+        22.0 / 525.0,
+        -1.0 / 40.0,
+    ];
+
+    // Continuous extension weights
+    const D: [f64; 7] = [
+        -12715105075.0 / 11282082432.0,
+        0.0,
+        87487479700.0 / 32700410799.0,
+        -10690763975.0 / 1880347072.0,
+        701980252875.0 / 199316789632.0,
+        -1453857185.0 / 822651844.0,
+        69997945.0 / 29380423.0,
+    ];
+
+    let mut solution = OdeSolution::new(t0, y0);
+    let direction = (t1 - t0).signum();
+    let mut t = t0;
+    let mut y = y0.to_vec();
+    let mut k1 = f(t, &y);
+    let mut h = direction * initial_step(&k1, &y, t1 - t0, rtol, atol);
+
+    while (t1 - t) * direction > 0.0 {
+        if h.abs() <= 16.0 * f64::EPSILON * t.abs() {
+            return Err(Error::other("Step size too small"));
+        }
+
+        // Don't step past the end of the interval
+        if (t + h - t1) * direction > 0.0 {
+            h = t1 - t;
+        }
+
+        let k2 = f(t + C[0] * h, &combine(&y, h, &[(A2[0], &k1)]));
+        let k3 = f(t + C[1] * h, &combine(&y, h, &[(A3[0], &k1), (A3[1], &k2)]));
+        let k4 = f(
+            t + C[2] * h,
+            &combine(&y, h, &[(A4[0], &k1), (A4[1], &k2), (A4[2], &k3)]),
+        );
+        let k5 = f(
+            t + C[3] * h,
+            &combine(
+                &y,
+                h,
+                &[(A5[0], &k1), (A5[1], &k2), (A5[2], &k3), (A5[3], &k4)],
+            ),
+        );
+        let k6 = f(
+            t + C[4] * h,
+            &combine(
+                &y,
+                h,
+                &[
+                    (A6[0], &k1),
+                    (A6[1], &k2),
+                    (A6[2], &k3),
+                    (A6[3], &k4),
+                    (A6[4], &k5),
+                ],
+            ),
+        );
+        let y_new = combine(
+            &y,
+            h,
+            &[
+                (B[0], &k1),
+                (B[2], &k3),
+                (B[3], &k4),
+                (B[4], &k5),
+                (B[5], &k6),
+            ],
+        );
+        let k7 = f(t + C[5] * h, &y_new);
+
+        let zeros = vec![0.0; y.len()];
+        let err_vec = combine(
+            &zeros,
+            h,
+            &[
+                (E[0], &k1),
+                (E[2], &k3),
+                (E[3], &k4),
+                (E[4], &k5),
+                (E[5], &k6),
+                (E[6], &k7),
+            ],
+        );
+        let err = error_norm(&err_vec, &y, &y_new, rtol, atol);
+
+        if err <= 1.0 {
+            // Store the continuous extension of the accepted step
+            let r2: Vec<f64> = (0..y.len()).map(|i| y_new[i] - y[i]).collect();
+            let r3: Vec<f64> = (0..y.len()).map(|i| h * k1[i] - r2[i]).collect();
+            let r4: Vec<f64> = (0..y.len()).map(|i| r2[i] - h * k7[i] - r3[i]).collect();
+            let r5 = combine(
+                &zeros,
+                h,
+                &[
+                    (D[0], &k1),
+                    (D[2], &k3),
+                    (D[3], &k4),
+                    (D[4], &k5),
+                    (D[5], &k6),
+                    (D[6], &k7),
+                ],
+            );
+            solution.dense.push([y.clone(), r2, r3, r4, r5]);
+
+            t += h;
+            y = y_new;
+            k1 = k7;
+
+            solution.t.push(t);
+            solution.y.push(y.clone());
+        }
+
+        // A NaN error estimate (e.g. a failed callback) shrinks the step as much as possible
+        let factor = if err.is_nan() {
+            0.2
+        } else {
+            (0.9 * err.powf(-0.2)).clamp(0.2, 10.0)
+        };
+        h *= factor;
+    }
+
+    Ok(solution)
+}
+
+// Adaptive Rosenbrock 2(3) method for stiff problems (Shampine & Reichelt)
+pub fn ode23s<F: Fn(f64, &[f64]) -> Vec<f64>>(
+    f: F,
+    t0: f64,
+    t1: f64,
+    y0: &[f64],
+    rtol: f64,
+    atol: f64,
+) -> Result<OdeSolution, Error> {
+    let d = 1.0 / (2.0 + SQRT_2);
+    let e32 = 6.0 + SQRT_2;
+    let n = y0.len();
+
+    let mut solution = OdeSolution::new(t0, y0);
+    let direction = (t1 - t0).signum();
+    let mut t = t0;
+    let mut y = y0.to_vec();
+    let mut f0 = f(t, &y);
+    let mut h = direction * initial_step(&f0, &y, t1 - t0, rtol, atol);
+
+    while (t1 - t) * direction > 0.0 {
+        if h.abs() <= 16.0 * f64::EPSILON * t.abs() {
+            return Err(Error::other("Step size too small"));
+        }
+
+        if (t + h - t1) * direction > 0.0 {
+            h = t1 - t;
+        }
+
+        // Jacobian and time derivative of the right-hand side
+        let jac = jacobian(|x: &[f64]| f(t, x), &y);
+        let delta_t = f64::EPSILON.sqrt() * f64::max(t.abs(), 1.0);
+        let f_dt = f(t + delta_t, &y);
+        let dfdt: Vec<f64> = (0..n)
+            .map(|i| h * d * (f_dt[i] - f0[i]) / delta_t)
+            .collect();
+
+        // W = I - h d J
+        let mut w = SquareMatrix::new(n);
+        for i in 1..=n {
+            for j in 1..=n {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                w.set(i, j, identity - h * d * jac.get(i, j));
+            }
+        }
+
+        let rhs1: Vec<f64> = (0..n).map(|i| f0[i] + dfdt[i]).collect();
+        let k1 = w.solve(&rhs1)?;
+
+        let f1 = f(t + 0.5 * h, &combine(&y, h, &[(0.5, &k1)]));
+        let rhs2: Vec<f64> = (0..n).map(|i| f1[i] - k1[i]).collect();
+        let k2: Vec<f64> = w
+            .solve(&rhs2)?
+            .iter()
+            .zip(&k1)
+            .map(|(a, b)| a + b)
+            .collect();
+
+        let y_new = combine(&y, h, &[(1.0, &k2)]);
+        let f2 = f(t + h, &y_new);
+
+        let rhs3: Vec<f64> = (0..n)
+            .map(|i| f2[i] - e32 * (k2[i] - f1[i]) - 2.0 * (k1[i] - f0[i]) + dfdt[i])
+            .collect();
+        let k3 = w.solve(&rhs3)?;
+
+        let err_vec: Vec<f64> = (0..n)
+            .map(|i| h / 6.0 * (k1[i] - 2.0 * k2[i] + k3[i]))
+            .collect();
+        let err = error_norm(&err_vec, &y, &y_new, rtol, atol);
+
+        if err <= 1.0 {
+            t += h;
+            y = y_new;
+            f0 = f2;
+
+            solution.t.push(t);
+            solution.y.push(y.clone());
+        }
+
+        let factor = if err.is_nan() {
+            0.2
+        } else {
+            (0.8 * err.powf(-1.0 / 3.0)).clamp(0.2, 5.0)
+        };
+        h *= factor;
+    }
+
+    Ok(solution)
+}
+
+//  ---------------------------------------------------
+//  WASM ENTRY POINTS
+//  The right-hand side is a JS callback (t, y) => dy/dt
+//  returning an Array or a Float64Array
+//  ---------------------------------------------------
+
+// Wraps a JS callback into a Rust right-hand side, remembering the first exception thrown
+// or the first derivative whose length doesn't match the state
+fn js_rhs<'a>(
+    f: &'a js_sys::Function,
+    failure: &'a RefCell<Option<JsValue>>,
+) -> impl Fn(f64, &[f64]) -> Vec<f64> + 'a {
+    move |t: f64, y: &[f64]| -> Vec<f64> {
+        let state = js_sys::Float64Array::from(y);
+        let error = match f.call2(&JsValue::NULL, &JsValue::from_f64(t), &state) {
+            Ok(value) => {
+                let dy = js_sys::Float64Array::new(&value).to_vec();
+                if dy.len() == y.len() {
+                    return dy;
+                }
+                JsValue::from_str(&format!(
+                    "The right-hand side returned {} values for a state of {}",
+                    dy.len(),
+                    y.len()
+                ))
+            }
+            Err(e) => e,
+        };
+        failure.borrow_mut().get_or_insert(error);
+        vec![f64::NAN; y.len()]
+    }
+}
+
+// Serializes a solution, optionally resampled on the requested times
+fn js_output(
+    solution: Result<OdeSolution, Error>,
+    failure: RefCell<Option<JsValue>>,
+    t_eval: Vec<f64>,
+) -> Result<String, JsValue> {
+    if let Some(e) = failure.into_inner() {
+        return Err(e);
+    }
+
+    let mut solution = solution.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    if !t_eval.is_empty() {
+        let mut resampled = OdeSolution::new(t_eval[0], &solution.y[0]);
+        resampled.t.clear();
+        resampled.y.clear();
+
+        for t in t_eval {
+            if let Some(y) = solution.eval(t) {
+                resampled.t.push(t);
+                resampled.y.push(y);
+            }
+        }
+
+        solution = resampled;
+    }
+
+    serde_json::to_string(&solution).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn solve_rk4(
+    f: &js_sys::Function,
+    t0: f64,
+    t1: f64,
+    y0: Vec<f64>,
+    steps: usize,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let solution = rk4(js_rhs(f, &failure), t0, t1, &y0, steps);
+    js_output(Ok(solution), failure, Vec::new())
+}
+
+#[wasm_bindgen]
+pub fn solve_ode45(
+    f: &js_sys::Function,
+    t0: f64,
+    t1: f64,
+    y0: Vec<f64>,
+    rtol: f64,
+    atol: f64,
+    t_eval: Vec<f64>,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let solution = ode45(js_rhs(f, &failure), t0, t1, &y0, rtol, atol);
+    js_output(solution, failure, t_eval)
+}
+
+#[wasm_bindgen]
+pub fn solve_ode23s(
+    f: &js_sys::Function,
+    t0: f64,
+    t1: f64,
+    y0: Vec<f64>,
+    rtol: f64,
+    atol: f64,
+    t_eval: Vec<f64>,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let solution = ode23s(js_rhs(f, &failure), t0, t1, &y0, rtol, atol);
+    js_output(solution, failure, t_eval)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn rk4_test() {
+        // Exponential decay: y(t) = exp(-t)
+        let decay = |_t: f64, y: &[f64]| -> Vec<f64> { vec![-y[0]] };
+        let solution = rk4(decay, 0.0, 1.0, &[1.0], 100);
+
+        // Round to 5 digits
+        let last = solution.y[100][0];
+        let last = (last * 100000.0).round() / 100000.0;
+        let exact = (f64::exp(-1.0) * 100000.0).round() / 100000.0;
+
+        assert_eq!(solution.t.len(), 101);
+        assert_eq!(last, exact);
+    }
+
+    #[test]
+    fn ode45_test() {
+        // Harmonic oscillator: y = (cos t, -sin t)
+        let oscillator = |_t: f64, y: &[f64]| -> Vec<f64> { vec![y[1], -y[0]] };
+        let solution = ode45(oscillator, 0.0, 2.0 * PI, &[1.0, 0.0], 1e-8, 1e-10).unwrap();
+
+        let last = solution.y.last().unwrap();
+        assert!((last[0] - 1.0).abs() < 1e-6);
+        assert!(last[1].abs() < 1e-6);
+
+        // Dense output between the steps
+        for t in [0.3, 1.7, 4.0, 5.5] {
+            let y = solution.eval(t).unwrap();
+            assert!((y[0] - t.cos()).abs() < 1e-6);
+            assert!((y[1] + t.sin()).abs() < 1e-6);
+        }
+
+        assert_eq!(solution.eval(7.0), None);
+
+        // Backwards integration
+        let decay = |_t: f64, y: &[f64]| -> Vec<f64> { vec![-y[0]] };
+        let solution = ode45(decay, 1.0, 0.0, &[f64::exp(-1.0)], 1e-8, 1e-10).unwrap();
+        let last = solution.y.last().unwrap()[0];
+        assert!((last - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ode23s_test() {
+        // Stiff problem whose smooth solution is y(t) = cos t
+        let stiff = |t: f64, y: &[f64]| -> Vec<f64> { vec![-1000.0 * (y[0] - t.cos()) - t.sin()] };
+        let solution = ode23s(stiff, 0.0, 10.0, &[1.0], 1e-3, 1e-6).unwrap();
+
+        let last = solution.y.last().unwrap()[0];
+        assert!((last - f64::cos(10.0)).abs() < 1e-4);
+
+        // The explicit solver is bound by stability and needs far more steps
+        let explicit = ode45(stiff, 0.0, 10.0, &[1.0], 1e-3, 1e-6).unwrap();
+        assert!(solution.t.len() * 5 < explicit.t.len());
+    }
+}
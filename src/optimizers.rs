@@ -1,97 +1,2336 @@
-use crate::calculus::differentiate;
+use crate::calculus::{gradient, jacobian};
+use crate::statistics::tinv;
+use crate::{Matrix, MatrixTraits, SquareMatrix};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use wasm_bindgen::prelude::*;
 
-pub fn maximum<F: Fn(f64) -> f64>(f: F, left: f64, right: f64, precision: f64) -> f64 {
-    let mut max = f(left);
-    let mut cur_position = left;
+// Outcome of a root finder
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Root {
+    pub x: f64,
+    pub fx: f64,
+    pub iterations: usize,
+}
+
+// Location and value of an extremum
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+pub struct Extremum {
+    pub x: f64,
+    pub fx: f64,
+    pub iterations: usize,
+}
+
+// Failures of the iterative solvers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OptimizerError {
+    // f(left) and f(right) have the same sign
+    NoBracket,
+    // The iteration budget ran out before reaching the tolerance
+    MaxIterations,
+    // No point satisfies all the constraints
+    Infeasible,
+    // The objective decreases without limit in the feasible region
+    Unbounded,
+    // Sizes of the problem data don't agree
+    DimensionMismatch,
+    // The search box is empty or not finite
+    InvalidBounds,
+    // The function returned NaN or an infinite value
+    NotFinite,
+    // The sampling step is not positive, or too small for the interval
+    InvalidStep,
+}
+
+impl fmt::Display for OptimizerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptimizerError::NoBracket => write!(f, "The interval does not bracket a sign change"),
+            OptimizerError::MaxIterations => write!(f, "Maximum number of iterations reached"),
+            OptimizerError::Infeasible => write!(f, "The problem has no feasible point"),
+            OptimizerError::Unbounded => write!(f, "The problem is unbounded"),
+            OptimizerError::DimensionMismatch => write!(f, "Mismatched problem dimensions"),
+            OptimizerError::InvalidBounds => {
+                write!(f, "The bounds must be finite, with lower <= upper")
+            }
+            OptimizerError::NotFinite => write!(f, "The function returned a non-finite value"),
+            OptimizerError::InvalidStep => {
+                write!(
+                    f,
+                    "The step must be positive and not too small for the interval"
+                )
+            }
+        }
+    }
+}
+
+impl Error for OptimizerError {}
+
+// How a multivariate optimizer stopped
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConvergenceStatus {
+    Converged,
+    MaxIterations,
+    // No step along the search direction satisfied the Wolfe conditions
+    LineSearchFailed,
+    // No damping of the step reduced the sum of squares
+    Stalled,
+}
+
+// Outcome of a multivariate optimizer
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiExtremum {
+    pub x: Vec<f64>,
+    pub fx: f64,
+    pub iterations: usize,
+    pub gradient_norm: f64,
+    pub status: ConvergenceStatus,
+}
+
+// Nature of a stationary point
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum StationaryKind {
+    Maximum,
+    Minimum,
+    // Stationary point where f' doesn't change sign, like x^3 in 0
+    Inflection,
+}
+
+// Location, value and nature of a point where f' vanishes
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+pub struct StationaryPoint {
+    pub x: f64,
+    pub fx: f64,
+    pub kind: StationaryKind,
+}
+
+const MAX_ITERATIONS: usize = 200;
+// Largest number of samples of a grid search
+const MAX_SAMPLES: f64 = 16_777_216.0;
+
+// Local maximum in [left, right] (Brent's method on -f)
+pub fn maximum<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Extremum, OptimizerError> {
+    let min = brent_minimize(|x: f64| -f(x), left, right, tol)?;
+
+    Ok(Extremum {
+        x: min.x,
+        fx: -min.fx,
+        iterations: min.iterations,
+    })
+}
+
+// Local minimum in [left, right] (Brent's method)
+pub fn minimum<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Extremum, OptimizerError> {
+    brent_minimize(f, left, right, tol)
+}
+
+// Stationary points in [left, right], sampling f' every `precision`
+pub fn stationary_points<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    precision: f64,
+) -> Result<Vec<StationaryPoint>, OptimizerError> {
+    let samples = ((right - left) / precision).ceil();
+    if !(precision > 0.0 && samples <= MAX_SAMPLES) {
+        return Err(OptimizerError::InvalidStep);
+    }
+    let samples = (samples as usize).max(1);
+    let step = (right - left) / samples as f64;
+
+    let derivative = |x: f64| -> f64 {
+        let h = f64::EPSILON.cbrt() * f64::max(x.abs(), 1.0);
+        (f(x + h) - f(x - h)) / (2.0 * h)
+    };
+
+    // f' is only numerically zero at stationary endpoints
+    let points = grid_zeros(&derivative, left, right, samples, f64::EPSILON.sqrt(), true)
+        .into_iter()
+        .map(|x| {
+            let fx = f(x);
+            let h = f64::EPSILON.powf(0.25) * f64::max(x.abs(), 1.0);
+            let second = (f(x + h) - 2.0 * fx + f(x - h)) / (h * h);
+
+            // A vanishing second derivative is inconclusive (x^4, x^3):
+            // fall back to the sign of f' on both sides of the point
+            let kind = if second < -1e-6 * f64::max(fx.abs(), 1.0) {
+                StationaryKind::Maximum
+            } else if second > 1e-6 * f64::max(fx.abs(), 1.0) {
+                StationaryKind::Minimum
+            } else {
+                let before = derivative(x - step);
+                let after = derivative(x + step);
+                if before > 0.0 && after < 0.0 {
+                    StationaryKind::Maximum
+                } else if before < 0.0 && after > 0.0 {
+                    StationaryKind::Minimum
+                } else {
+                    StationaryKind::Inflection
+                }
+            };
+
+            StationaryPoint { x, fx, kind }
+        })
+        .collect();
+
+    Ok(points)
+}
+
+// Golden-section search for a minimum of a unimodal function
+pub fn golden_section<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Extremum, OptimizerError> {
+    let inv_phi = (f64::sqrt(5.0) - 1.0) * 0.5;
+
+    let mut a = left;
+    let mut b = right;
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let mut fc = f(c);
+    let mut fd = f(d);
+
+    for iteration in 1..=MAX_ITERATIONS {
+        if (b - a).abs() <= tol + f64::EPSILON.sqrt() * (c.abs() + d.abs()) {
+            let (x, fx) = if fc < fd { (c, fc) } else { (d, fd) };
+            return Ok(Extremum {
+                x,
+                fx,
+                iterations: iteration,
+            });
+        }
+
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - inv_phi * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + inv_phi * (b - a);
+            fd = f(d);
+        }
+    }
+
+    Err(OptimizerError::MaxIterations)
+}
+
+// Brent's minimizer: golden-section steps accelerated by parabolic interpolation
+pub fn brent_minimize<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Extremum, OptimizerError> {
+    let golden = (3.0 - f64::sqrt(5.0)) * 0.5;
+
+    let mut a = f64::min(left, right);
+    let mut b = f64::max(left, right);
+    let mut x = a + golden * (b - a);
+    let mut w = x;
+    let mut v = x;
+    let mut fx = f(x);
+    let mut fw = fx;
+    let mut fv = fx;
+    let mut d: f64 = 0.0;
+    let mut e: f64 = 0.0;
+
+    for iteration in 1..=MAX_ITERATIONS {
+        let m = 0.5 * (a + b);
+        let tol1 = f64::EPSILON.sqrt() * x.abs() + tol / 3.0;
+        let tol2 = 2.0 * tol1;
+
+        if (x - m).abs() <= tol2 - 0.5 * (b - a) {
+            return Ok(Extremum {
+                x,
+                fx,
+                iterations: iteration,
+            });
+        }
+
+        let mut p = 0.0;
+        let mut q = 0.0;
+        let mut r = 0.0;
+
+        if e.abs() > tol1 {
+            // Fit a parabola through x, w and v
+            r = (x - w) * (fx - fv);
+            q = (x - v) * (fx - fw);
+            p = (x - v) * q - (x - w) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            } else {
+                q = -q;
+            }
+            r = e;
+            e = d;
+        }
+
+        if p.abs() < (0.5 * q * r).abs() && p > q * (a - x) && p < q * (b - x) {
+            // Parabolic interpolation step
+            d = p / q;
+            let u = x + d;
+            if u - a < tol2 || b - u < tol2 {
+                d = if x < m { tol1 } else { -tol1 };
+            }
+        } else {
+            // Golden-section step
+            e = if x < m { b - x } else { a - x };
+            d = golden * e;
+        }
+
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else {
+            x + tol1.copysign(d)
+        };
+        let fu = f(u);
+
+        if fu <= fx {
+            if u < x {
+                b = x;
+            } else {
+                a = x;
+            }
+            v = w;
+            fv = fw;
+            w = x;
+            fw = fx;
+            x = u;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+
+    Err(OptimizerError::MaxIterations)
+}
+
+// Brent's root finder: inverse quadratic interpolation, secant and bisection steps
+pub fn fzero<F: Fn(f64) -> f64>(
+    f: F,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Root, OptimizerError> {
+    let mut a = left;
+    let mut b = right;
+    let mut fa = f(a);
+    let mut fb = f(b);
+
+    if fa == 0.0 {
+        return Ok(Root {
+            x: a,
+            fx: fa,
+            iterations: 0,
+        });
+    }
+    if fb == 0.0 {
+        return Ok(Root {
+            x: b,
+            fx: fb,
+            iterations: 0,
+        });
+    }
+    if fa.is_nan() || fb.is_nan() || fa.signum() == fb.signum() {
+        return Err(OptimizerError::NoBracket);
+    }
+
+    let mut c = a;
+    let mut fc = fa;
+    let mut d = b - a;
+    let mut e = d;
+
+    for iteration in 1..=MAX_ITERATIONS {
+        // Keep the root between b and c
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+
+        // b is the best approximation so far
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let m = 0.5 * (c - b);
+
+        if m.abs() <= tol1 || fb == 0.0 {
+            return Ok(Root {
+                x: b,
+                fx: fb,
+                iterations: iteration,
+            });
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // Try interpolating
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant step
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                // Inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            // Accept the interpolation only if it stays well inside the bracket
+            if 2.0 * p < f64::min(3.0 * m * q - (tol1 * q).abs(), (e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            // Bisection
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(m) };
+        fb = f(b);
+    }
+
+    Err(OptimizerError::MaxIterations)
+}
+
+// Every root in [left, right]: sign changes on a sampling grid refined with Brent's method,
+// plus tangential roots where |f| has a local minimum touching zero
+pub fn fzeros<F: Fn(f64) -> f64>(f: F, left: f64, right: f64, tol: f64) -> Vec<f64> {
+    grid_zeros(&f, left, right, 2048, tol, false)
+}
+
+// Scans `samples` intervals for sign changes and tangential roots, refining each with fzero.
+// Endpoints are roots if f vanishes there, or with `approximate_endpoints`, if |f| is negligible.
+fn grid_zeros<F: Fn(f64) -> f64>(
+    f: &F,
+    left: f64,
+    right: f64,
+    samples: usize,
+    tol: f64,
+    approximate_endpoints: bool,
+) -> Vec<f64> {
+    let step = (right - left) / samples as f64;
+
+    let xs: Vec<f64> = (0..=samples).map(|i| left + step * i as f64).collect();
+    let fs: Vec<f64> = xs.iter().map(|&x| f(x)).collect();
+
+    // Tolerance on |f| used to accept tangential roots
+    let scale = fs
+        .iter()
+        .filter(|y| y.is_finite())
+        .fold(0.0, |acc: f64, y| acc.max(y.abs()));
+    let f_tol = f64::EPSILON.sqrt() * f64::max(scale, 1.0);
+
+    // Central difference derivative for locating the extremum of a tangential root
+    let derivative = |x: f64| -> f64 {
+        let h = 1e-6 * f64::max(x.abs(), 1.0);
+        (f(x + h) - f(x - h)) / (2.0 * h)
+    };
+
+    let endpoint_tol = if approximate_endpoints { f_tol } else { 0.0 };
+    let mut roots: Vec<f64> = Vec::new();
+
+    if fs[0].abs() <= endpoint_tol {
+        roots.push(xs[0]);
+    }
+
+    for i in 0..samples {
+        if fs[i] == 0.0 {
+            roots.push(xs[i]);
+        } else if fs[i] * fs[i + 1] < 0.0 {
+            if let Ok(root) = fzero(f, xs[i], xs[i + 1], tol) {
+                roots.push(root.x);
+            }
+        } else if i > 0
+            && fs[i] * fs[i - 1] > 0.0
+            && fs[i].abs() <= fs[i - 1].abs()
+            && fs[i].abs() <= fs[i + 1].abs()
+        {
+            if let Ok(extremum) = fzero(derivative, xs[i - 1], xs[i + 1], tol) {
+                if f(extremum.x).abs() <= f_tol {
+                    roots.push(extremum.x);
+                }
+            }
+        }
+    }
+
+    if fs[samples].abs() <= endpoint_tol {
+        roots.push(xs[samples]);
+    }
+
+    // Neighbouring intervals can report the same root
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    roots.dedup_by(|a, b| (*a - *b).abs() <= f64::max(tol, step * 1e-3));
+
+    roots
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+// Accepted point of a line search
+struct LineStep {
+    x: Vec<f64>,
+    fx: f64,
+    g: Vec<f64>,
+}
+
+// Strong Wolfe line search along p (Nocedal & Wright, algorithms 3.5 and 3.6)
+fn line_search<F: Fn(&[f64]) -> f64>(
+    f: &F,
+    x: &[f64],
+    fx: f64,
+    g: &[f64],
+    p: &[f64],
+) -> Option<LineStep> {
+    let c1 = 1e-4;
+    let c2 = 0.9;
+    let dphi0 = dot(g, p);
+
+    if dphi0 >= 0.0 {
+        return None;
+    }
+
+    let point =
+        |alpha: f64| -> Vec<f64> { x.iter().zip(p).map(|(xi, pi)| xi + alpha * pi).collect() };
+    let evaluate = |alpha: f64| -> LineStep {
+        let x = point(alpha);
+        LineStep {
+            fx: f(&x),
+            g: gradient(f, &x),
+            x,
+        }
+    };
+
+    // Narrows [lo, hi] down to a point satisfying the strong Wolfe conditions
+    let zoom = |mut lo: f64, mut hi: f64, mut phi_lo: f64, mut dphi_lo: f64, mut phi_hi: f64| {
+        let mut best: Option<LineStep> = None;
+
+        for _iteration in 0..50 {
+            // Safeguarded quadratic interpolation
+            let width = hi - lo;
+            let mut alpha =
+                lo - dphi_lo * width * width / (2.0 * (phi_hi - phi_lo - dphi_lo * width));
+            let (low, high) = (f64::min(lo, hi), f64::max(lo, hi));
+            if !(alpha > low + 0.1 * (high - low) && alpha < high - 0.1 * (high - low)) {
+                alpha = 0.5 * (lo + hi);
+            }
+
+            let step = evaluate(alpha);
+
+            if step.fx > fx + c1 * alpha * dphi0 || step.fx >= phi_lo {
+                hi = alpha;
+                phi_hi = step.fx;
+            } else {
+                let dphi = dot(&step.g, p);
+                if dphi.abs() <= -c2 * dphi0 {
+                    return Some(step);
+                }
+                if dphi * (hi - lo) >= 0.0 {
+                    hi = lo;
+                    phi_hi = phi_lo;
+                }
+                lo = alpha;
+                phi_lo = step.fx;
+                dphi_lo = dphi;
+                best = Some(step);
+            }
+        }
+
+        // Settle for sufficient decrease
+        best
+    };
+
+    let mut alpha_prev = 0.0;
+    let mut phi_prev = fx;
+    let mut dphi_prev = dphi0;
+    let mut alpha = 1.0;
+
+    for iteration in 0..50 {
+        let step = evaluate(alpha);
+
+        if step.fx > fx + c1 * alpha * dphi0 || (iteration > 0 && step.fx >= phi_prev) {
+            return zoom(alpha_prev, alpha, phi_prev, dphi_prev, step.fx);
+        }
+
+        let dphi = dot(&step.g, p);
+        if dphi.abs() <= -c2 * dphi0 {
+            return Some(step);
+        }
+        if dphi >= 0.0 {
+            return zoom(alpha, alpha_prev, step.fx, dphi, phi_prev);
+        }
+
+        alpha_prev = alpha;
+        phi_prev = step.fx;
+        dphi_prev = dphi;
+        alpha *= 2.0;
+    }
+
+    None
+}
+
+// Objective at the start of a multivariate minimization, which needs a non-empty starting
+// point where the objective is finite
+fn start_value<F: Fn(&[f64]) -> f64>(f: &F, x0: &[f64]) -> Result<f64, OptimizerError> {
+    if x0.is_empty() {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+    let fx = f(x0);
+    if !fx.is_finite() {
+        return Err(OptimizerError::NotFinite);
+    }
+    Ok(fx)
+}
+
+// Derivative-free simplex search, with the same initial simplex and stopping rules as fminsearch
+pub fn nelder_mead<F: Fn(&[f64]) -> f64>(
+    f: F,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<MultiExtremum, OptimizerError> {
+    let n = x0.len();
+    let f0 = start_value(&f, x0)?;
+
+    let mut simplex: Vec<Vec<f64>> = vec![x0.to_vec()];
+    for i in 0..n {
+        let mut vertex = x0.to_vec();
+        vertex[i] = if x0[i] != 0.0 { 1.05 * x0[i] } else { 0.00025 };
+        simplex.push(vertex);
+    }
+    let mut values: Vec<f64> = std::iter::once(f0)
+        .chain(simplex[1..].iter().map(|v| f(v)))
+        .collect();
+
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        // Order the vertices from best to worst
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&a, &b| {
+            values[a]
+                .partial_cmp(&values[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        let f_spread = values
+            .iter()
+            .map(|v| (v - values[0]).abs())
+            .fold(0.0, f64::max);
+        let x_spread = simplex
+            .iter()
+            .flat_map(|v| v.iter().zip(&simplex[0]).map(|(a, b)| (a - b).abs()))
+            .fold(0.0, f64::max);
+        if f_spread <= tol && x_spread <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+
+        iterations += 1;
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64)
+            .collect();
+        let along = |t: f64| -> Vec<f64> {
+            (0..n)
+                .map(|j| centroid[j] + t * (simplex[n][j] - centroid[j]))
+                .collect()
+        };
+
+        let reflected = along(-1.0);
+        let f_reflected = f(&reflected);
+
+        if f_reflected < values[0] {
+            let expanded = along(-2.0);
+            let f_expanded = f(&expanded);
+            if f_expanded < f_reflected {
+                simplex[n] = expanded;
+                values[n] = f_expanded;
+            } else {
+                simplex[n] = reflected;
+                values[n] = f_reflected;
+            }
+            continue;
+        }
+
+        if f_reflected < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = f_reflected;
+            continue;
+        }
+
+        // Outside or inside contraction
+        let (contracted, limit) = if f_reflected < values[n] {
+            (along(-0.5), f_reflected)
+        } else {
+            (along(0.5), values[n])
+        };
+        let f_contracted = f(&contracted);
+
+        if f_contracted < limit {
+            simplex[n] = contracted;
+            values[n] = f_contracted;
+            continue;
+        }
+
+        // Shrink towards the best vertex
+        for i in 1..=n {
+            simplex[i] = (0..n)
+                .map(|j| simplex[0][j] + 0.5 * (simplex[i][j] - simplex[0][j]))
+                .collect();
+            values[i] = f(&simplex[i]);
+        }
+    }
+
+    let best = (0..=n)
+        .min_by(|&a, &b| {
+            values[a]
+                .partial_cmp(&values[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap();
+
+    Ok(MultiExtremum {
+        gradient_norm: norm(&gradient(&f, &simplex[best])),
+        x: simplex[best].clone(),
+        fx: values[best],
+        iterations,
+        status,
+    })
+}
+
+// Quasi-Newton method with a dense inverse Hessian approximation
+pub fn bfgs<F: Fn(&[f64]) -> f64>(
+    f: F,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<MultiExtremum, OptimizerError> {
+    let n = x0.len();
+    let mut x = x0.to_vec();
+    let mut fx = start_value(&f, x0)?;
+    let mut g = gradient(&f, &x);
+    let mut h = SquareMatrix::identity(n);
+
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        if norm(&g) <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+
+        let mut p: Vec<f64> = h.mul_vec(&g).iter().map(|v| -v).collect();
+
+        // Restart from steepest descent if the approximation lost positive definiteness
+        if dot(&p, &g) >= 0.0 {
+            h = SquareMatrix::identity(n);
+            p = g.iter().map(|v| -v).collect();
+        }
+
+        let step = match line_search(&f, &x, fx, &g, &p) {
+            Some(step) => step,
+            None => {
+                status = ConvergenceStatus::LineSearchFailed;
+                break;
+            }
+        };
+
+        iterations += 1;
+
+        let s: Vec<f64> = (0..n).map(|i| step.x[i] - x[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| step.g[i] - g[i]).collect();
+        let sy = dot(&s, &y);
+
+        if sy > f64::EPSILON * norm(&s) * norm(&y) {
+            // Scale the initial approximation before the first update
+            if iterations == 1 {
+                h = SquareMatrix::identity(n);
+                let scale = sy / dot(&y, &y);
+                for i in 1..=n {
+                    h.set(i, i, scale);
+                }
+            }
+
+            // H = (I - rho s y') H (I - rho y s') + rho s s'
+            let rho = 1.0 / sy;
+            let hy = h.mul_vec(&y);
+            let yhy = dot(&y, &hy);
+            for i in 1..=n {
+                for j in 1..=n {
+                    let update = (1.0 + rho * yhy) * s[i - 1] * s[j - 1]
+                        - hy[i - 1] * s[j - 1]
+                        - s[i - 1] * hy[j - 1];
+                    h.set(i, j, h.get(i, j) + rho * update);
+                }
+            }
+        }
+
+        x = step.x;
+        fx = step.fx;
+        g = step.g;
+    }
+
+    Ok(MultiExtremum {
+        gradient_norm: norm(&g),
+        x,
+        fx,
+        iterations,
+        status,
+    })
+}
+
+// Limited-memory BFGS, keeping only the last `memory` correction pairs (at least one)
+pub fn lbfgs<F: Fn(&[f64]) -> f64>(
+    f: F,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+    memory: usize,
+) -> Result<MultiExtremum, OptimizerError> {
+    let n = x0.len();
+    let memory = memory.max(1);
+    let mut x = x0.to_vec();
+    let mut fx = start_value(&f, x0)?;
+    let mut g = gradient(&f, &x);
+    let mut pairs: VecDeque<(Vec<f64>, Vec<f64>, f64)> = VecDeque::new();
+
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        if norm(&g) <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+
+        // Two-loop recursion
+        let mut q = g.clone();
+        let mut alphas = vec![0.0; pairs.len()];
+        for (k, (s, y, rho)) in pairs.iter().enumerate().rev() {
+            alphas[k] = rho * dot(s, &q);
+            for i in 0..n {
+                q[i] -= alphas[k] * y[i];
+            }
+        }
+
+        let gamma = match pairs.back() {
+            Some((s, y, _)) => dot(s, y) / dot(y, y),
+            None => 1.0,
+        };
+        let mut r: Vec<f64> = q.iter().map(|v| gamma * v).collect();
+
+        for (k, (s, y, rho)) in pairs.iter().enumerate() {
+            let beta = rho * dot(y, &r);
+            for i in 0..n {
+                r[i] += s[i] * (alphas[k] - beta);
+            }
+        }
+
+        let mut p: Vec<f64> = r.iter().map(|v| -v).collect();
+        if dot(&p, &g) >= 0.0 {
+            pairs.clear();
+            p = g.iter().map(|v| -v).collect();
+        }
+
+        let step = match line_search(&f, &x, fx, &g, &p) {
+            Some(step) => step,
+            None => {
+                status = ConvergenceStatus::LineSearchFailed;
+                break;
+            }
+        };
+
+        iterations += 1;
+
+        let s: Vec<f64> = (0..n).map(|i| step.x[i] - x[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| step.g[i] - g[i]).collect();
+        let sy = dot(&s, &y);
+
+        if sy > f64::EPSILON * norm(&s) * norm(&y) {
+            if pairs.len() == memory {
+                pairs.pop_front();
+            }
+            pairs.push_back((s, y, 1.0 / sy));
+        }
+
+        x = step.x;
+        fx = step.fx;
+        g = step.g;
+    }
+
+    Ok(MultiExtremum {
+        gradient_norm: norm(&g),
+        x,
+        fx,
+        iterations,
+        status,
+    })
+}
+
+// Inequality constraint g(x) <= 0
+pub type Constraint<'a> = &'a dyn Fn(&[f64]) -> f64;
+
+// Constrained minimization through the augmented Lagrangian method (PHR).
+// Constraints are written as g(x) <= 0; use infinite bounds for free variables.
+pub fn augmented_lagrangian<F: Fn(&[f64]) -> f64>(
+    f: F,
+    constraints: &[Constraint],
+    lower: &[f64],
+    upper: &[f64],
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<MultiExtremum, OptimizerError> {
+    let n = x0.len();
+    if lower.len() != n || upper.len() != n {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+
+    // Bounds become ordinary inequality constraints
+    let violations = |x: &[f64]| -> Vec<f64> {
+        let mut g: Vec<f64> = constraints.iter().map(|c| c(x)).collect();
+        for j in 0..n {
+            if lower[j].is_finite() {
+                g.push(lower[j] - x[j]);
+            }
+            if upper[j].is_finite() {
+                g.push(x[j] - upper[j]);
+            }
+        }
+        g
+    };
+
+    let mut x = x0.to_vec();
+    let mut multipliers = vec![0.0; violations(&x).len()];
+    let mut penalty = 10.0;
+    let mut previous_violation = f64::INFINITY;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        iterations += 1;
+
+        let lagrangian = |x: &[f64]| -> f64 {
+            let g = violations(x);
+            let shifted: f64 = (0..g.len())
+                .map(|i| {
+                    f64::max(0.0, multipliers[i] + penalty * g[i]).powi(2) - multipliers[i].powi(2)
+                })
+                .sum();
+            f(x) + shifted / (2.0 * penalty)
+        };
+
+        let inner = lbfgs(lagrangian, &x, tol, 500, 10)?;
+        let step = norm(&(0..n).map(|j| inner.x[j] - x[j]).collect::<Vec<f64>>());
+        x = inner.x;
+
+        let g = violations(&x);
+        let violation = g.iter().fold(0.0, |acc: f64, gi| acc.max(*gi));
+
+        if violation <= tol && step <= tol.sqrt() * f64::max(norm(&x), 1.0) {
+            return Ok(MultiExtremum {
+                fx: f(&x),
+                gradient_norm: inner.gradient_norm,
+                x,
+                iterations,
+                status: ConvergenceStatus::Converged,
+            });
+        }
+
+        // First order multiplier update, tighter penalty when feasibility stalls
+        for i in 0..g.len() {
+            multipliers[i] = f64::max(0.0, multipliers[i] + penalty * g[i]);
+        }
+        if violation > 0.25 * previous_violation {
+            penalty *= 10.0;
+        }
+        previous_violation = violation;
+    }
+
+    Ok(MultiExtremum {
+        fx: f(&x),
+        gradient_norm: norm(&gradient(&f, &x)),
+        x,
+        iterations,
+        status: ConvergenceStatus::MaxIterations,
+    })
+}
+
+// Linear program: minimize Objective' x subject to
+// InequalityMatrix x <= InequalityVector, EqualityMatrix x = EqualityVector, x >= 0
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LinearProgram {
+    pub objective: Vec<f64>,
+    #[serde(default)]
+    pub inequality_matrix: Option<Matrix>,
+    #[serde(default)]
+    pub inequality_vector: Vec<f64>,
+    #[serde(default)]
+    pub equality_matrix: Option<Matrix>,
+    #[serde(default)]
+    pub equality_vector: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LinearSolution {
+    pub x: Vec<f64>,
+    pub fx: f64,
+    pub iterations: usize,
+}
+
+const SIMPLEX_EPS: f64 = 1e-9;
+
+// Pivots the tableau on (row, col), both 1-based
+fn pivot(tableau: &mut Matrix, row: usize, col: usize) {
+    let rows = tableau.sizey;
+    let cols = tableau.sizex;
+
+    let value = tableau.get(row, col);
+    for j in 1..=cols {
+        tableau.set(row, j, tableau.get(row, j) / value);
+    }
+
+    for i in 1..=rows {
+        let factor = tableau.get(i, col);
+        if i != row && factor != 0.0 {
+            for j in 1..=cols {
+                tableau.set(i, j, tableau.get(i, j) - factor * tableau.get(row, j));
+            }
+        }
+    }
+}
+
+// Simplex iterations with Bland's rule on the first `usable` columns.
+// The last row holds the reduced costs, the last column the right-hand side.
+fn simplex_iterations(
+    tableau: &mut Matrix,
+    basis: &mut [usize],
+    usable: usize,
+    iterations: &mut usize,
+) -> Result<(), OptimizerError> {
+    let m = basis.len();
+    let rhs = tableau.sizex;
+    let cost_row = m + 1;
+
+    loop {
+        // Entering variable: lowest index with a negative reduced cost
+        let entering = match (1..=usable).find(|&j| tableau.get(cost_row, j) < -SIMPLEX_EPS) {
+            Some(j) => j,
+            None => return Ok(()),
+        };
+
+        // Leaving variable: minimum ratio, ties broken by the lowest basic index
+        let mut leaving: Option<usize> = None;
+        for i in 1..=m {
+            let a = tableau.get(i, entering);
+            if a > SIMPLEX_EPS {
+                let ratio = tableau.get(i, rhs) / a;
+                leaving = match leaving {
+                    None => Some(i),
+                    Some(l) => {
+                        let best = tableau.get(l, rhs) / tableau.get(l, entering);
+                        if ratio < best - SIMPLEX_EPS
+                            || (ratio <= best + SIMPLEX_EPS && basis[i - 1] < basis[l - 1])
+                        {
+                            Some(i)
+                        } else {
+                            Some(l)
+                        }
+                    }
+                };
+            }
+        }
+
+        let leaving = leaving.ok_or(OptimizerError::Unbounded)?;
+
+        *iterations += 1;
+        if *iterations > 50 * (rhs + m) {
+            return Err(OptimizerError::MaxIterations);
+        }
+
+        pivot(tableau, leaving, entering);
+        basis[leaving - 1] = entering;
+    }
+}
+
+// Two-phase simplex method on a dense tableau
+pub fn linprog(problem: &LinearProgram) -> Result<LinearSolution, OptimizerError> {
+    let n = problem.objective.len();
+    let empty = Matrix::new(n, 0);
+    let a_ub = problem.inequality_matrix.as_ref().unwrap_or(&empty);
+    let a_eq = problem.equality_matrix.as_ref().unwrap_or(&empty);
+    let m_ub = a_ub.sizey;
+    let m_eq = a_eq.sizey;
+
+    if a_ub.sizex != n
+        || a_eq.sizex != n
+        || a_ub.matrix.len() != a_ub.sizex * m_ub
+        || a_eq.matrix.len() != a_eq.sizex * m_eq
+        || problem.inequality_vector.len() != m_ub
+        || problem.equality_vector.len() != m_eq
+    {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+
+    // Columns: original variables, one slack per inequality, one artificial per row
+    let m = m_ub + m_eq;
+    let slack = n;
+    let artificial = n + m_ub;
+    let rhs = n + m_ub + m + 1;
+    let mut tableau = Matrix::new(rhs, m + 1);
+    let mut basis = vec![0; m];
+
+    for i in 1..=m {
+        let (source, row, b) = if i <= m_ub {
+            (a_ub, i, problem.inequality_vector[i - 1])
+        } else {
+            (a_eq, i - m_ub, problem.equality_vector[i - m_ub - 1])
+        };
+
+        // Keep the right-hand side non-negative
+        let sign = if b < 0.0 { -1.0 } else { 1.0 };
+        for j in 1..=n {
+            tableau.set(i, j, sign * source.get(row, j));
+        }
+        if i <= m_ub {
+            tableau.set(i, slack + i, sign);
+        }
+        tableau.set(i, artificial + i, 1.0);
+        tableau.set(i, rhs, sign * b);
+
+        // A slack with a positive coefficient is already a feasible basic variable
+        basis[i - 1] = if i <= m_ub && sign > 0.0 {
+            slack + i
+        } else {
+            artificial + i
+        };
+    }
+
+    // Phase 1: minimize the sum of the artificial variables in the basis
+    let cost_row = m + 1;
+    for i in 1..=m {
+        if basis[i - 1] > artificial {
+            for j in 1..=rhs {
+                if j <= artificial || j == rhs {
+                    tableau.set(cost_row, j, tableau.get(cost_row, j) - tableau.get(i, j));
+                }
+            }
+        }
+    }
+
+    let mut iterations = 0;
+    simplex_iterations(&mut tableau, &mut basis, rhs - 1, &mut iterations)?;
+
+    if -tableau.get(cost_row, rhs) > SIMPLEX_EPS * f64::max(1.0, n as f64) {
+        return Err(OptimizerError::Infeasible);
+    }
+
+    // Drive degenerate artificial variables out of the basis
+    for i in 1..=m {
+        if basis[i - 1] > artificial {
+            if let Some(j) = (1..=artificial).find(|&j| tableau.get(i, j).abs() > SIMPLEX_EPS) {
+                pivot(&mut tableau, i, j);
+                basis[i - 1] = j;
+            }
+        }
+    }
+
+    // Phase 2: reduced costs of the original objective
+    for j in 1..=rhs {
+        let cost = if j <= n {
+            problem.objective[j - 1]
+        } else {
+            0.0
+        };
+        tableau.set(cost_row, j, cost);
+    }
+    for i in 1..=m {
+        let cost = if basis[i - 1] <= n {
+            problem.objective[basis[i - 1] - 1]
+        } else {
+            0.0
+        };
+        if cost != 0.0 {
+            for j in 1..=rhs {
+                tableau.set(
+                    cost_row,
+                    j,
+                    tableau.get(cost_row, j) - cost * tableau.get(i, j),
+                );
+            }
+        }
+    }
+
+    simplex_iterations(&mut tableau, &mut basis, artificial, &mut iterations)?;
+
+    let mut x = vec![0.0; n];
+    for i in 1..=m {
+        if basis[i - 1] <= n {
+            x[basis[i - 1] - 1] = tableau.get(i, rhs);
+        }
+    }
+
+    Ok(LinearSolution {
+        fx: dot(&problem.objective, &x),
+        x,
+        iterations,
+    })
+}
+
+// Nonlinear least squares fit of a model y = model(x, parameters)
+#[derive(Debug, PartialEq, Clone)]
+pub struct CurveFit {
+    pub parameters: Vec<f64>,
+    // ydata - model(xdata, parameters)
+    pub residuals: Vec<f64>,
+    // Asymptotic covariance of the parameters: s^2 (J'J)^-1
+    pub covariance: Matrix,
+    pub degrees_of_freedom: usize,
+    pub iterations: usize,
+    pub status: ConvergenceStatus,
+}
+
+impl CurveFit {
+    // Two-sided confidence intervals (lower, upper) from the t distribution, e.g. level = 0.95
+    pub fn confidence_intervals(&self, level: f64) -> Vec<(f64, f64)> {
+        let t = tinv(0.5 + 0.5 * level, self.degrees_of_freedom as f64);
+
+        (0..self.parameters.len())
+            .map(|j| {
+                let half_width = t * self.covariance.get(j + 1, j + 1).sqrt();
+                (
+                    self.parameters[j] - half_width,
+                    self.parameters[j] + half_width,
+                )
+            })
+            .collect()
+    }
+}
+
+// Levenberg-Marquardt method with Marquardt's diagonal scaling
+pub fn curve_fit<F: Fn(f64, &[f64]) -> f64>(
+    model: F,
+    xdata: &[f64],
+    ydata: &[f64],
+    p0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<CurveFit, OptimizerError> {
+    let m = xdata.len();
+    let n = p0.len();
+
+    if ydata.len() != m || m <= n {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+
+    let residuals =
+        |p: &[f64]| -> Vec<f64> { (0..m).map(|i| ydata[i] - model(xdata[i], p)).collect() };
+
+    // Forward-difference Jacobian of the model, one row per data point
+    let jacobian = |p: &[f64], fitted: &[f64]| -> Matrix {
+        let mut jac = Matrix::new(n, m);
+        let mut shifted = p.to_vec();
+
+        for j in 0..n {
+            let delta = f64::EPSILON.sqrt() * f64::max(p[j].abs(), 1.0);
+            shifted[j] = p[j] + delta;
+            for i in 0..m {
+                jac.set(
+                    i + 1,
+                    j + 1,
+                    (model(xdata[i], &shifted) - fitted[i]) / delta,
+                );
+            }
+            shifted[j] = p[j];
+        }
+
+        jac
+    };
+
+    // J'J and J'r
+    let normal_equations = |jac: &Matrix, r: &[f64]| -> (SquareMatrix, Vec<f64>) {
+        let mut jtj = SquareMatrix::new(n);
+        let mut jtr = vec![0.0; n];
+
+        for a in 1..=n {
+            for b in 1..=n {
+                jtj.set(a, b, (1..=m).map(|i| jac.get(i, a) * jac.get(i, b)).sum());
+            }
+            jtr[a - 1] = (1..=m).map(|i| jac.get(i, a) * r[i - 1]).sum();
+        }
+
+        (jtj, jtr)
+    };
+
+    let mut p = p0.to_vec();
+    let mut r = residuals(&p);
+    let mut cost = dot(&r, &r);
+    let mut lambda = 1e-3;
+
+    if !cost.is_finite() {
+        return Err(OptimizerError::NotFinite);
+    }
+
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        let fitted: Vec<f64> = (0..m).map(|i| ydata[i] - r[i]).collect();
+        let (jtj, jtr) = normal_equations(&jacobian(&p, &fitted), &r);
+
+        if !jtr.iter().all(|g| g.is_finite()) {
+            return Err(OptimizerError::NotFinite);
+        }
+        if max_norm(&jtr) <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+
+        iterations += 1;
+
+        // Increase the damping until the step reduces the sum of squares
+        let mut accepted = false;
+        let mut step = vec![0.0; n];
+        while lambda < 1e16 {
+            let mut damped = jtj.clone();
+            for j in 1..=n {
+                damped.set(j, j, jtj.get(j, j) * (1.0 + lambda));
+            }
+
+            if let Ok(delta) = damped.solve(&jtr) {
+                let candidate: Vec<f64> = (0..n).map(|j| p[j] + delta[j]).collect();
+                let r_candidate = residuals(&candidate);
+                let cost_candidate = dot(&r_candidate, &r_candidate);
+
+                if cost_candidate < cost {
+                    step = delta;
+                    p = candidate;
+                    r = r_candidate;
+                    cost = cost_candidate;
+                    lambda = f64::max(lambda / 10.0, 1e-12);
+                    accepted = true;
+                    break;
+                }
+            }
+
+            lambda *= 10.0;
+        }
+
+        if !accepted {
+            status = ConvergenceStatus::Stalled;
+            break;
+        }
+
+        if norm(&step) <= tol * (norm(&p) + tol) {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+    }
+
+    // Covariance from the inverse of J'J, column by column
+    let fitted: Vec<f64> = (0..m).map(|i| ydata[i] - r[i]).collect();
+    let (jtj, _) = normal_equations(&jacobian(&p, &fitted), &r);
+    let variance = cost / (m - n) as f64;
+    let mut covariance = Matrix::new(n, n);
+
+    for j in 1..=n {
+        let mut unit = vec![0.0; n];
+        unit[j - 1] = 1.0;
+        let column = jtj.solve(&unit).unwrap_or_else(|_| vec![f64::NAN; n]);
+        for i in 1..=n {
+            covariance.set(i, j, variance * column[i - 1]);
+        }
+    }
+
+    Ok(CurveFit {
+        parameters: p,
+        residuals: r,
+        covariance,
+        degrees_of_freedom: m - n,
+        iterations,
+        status,
+    })
+}
+
+// Outcome of a solver for systems of equations F(x) = 0
+#[derive(Debug, PartialEq, Clone)]
+pub struct SystemSolution {
+    pub x: Vec<f64>,
+    pub fx: Vec<f64>,
+    pub residual_norm: f64,
+    pub iterations: usize,
+    pub status: ConvergenceStatus,
+}
+
+fn max_norm(a: &[f64]) -> f64 {
+    a.iter().fold(0.0, |acc: f64, v| acc.max(v.abs()))
+}
+
+// Solves J p = -F, falling back to steepest descent on |F|^2 when J is singular
+fn newton_direction(jac: &SquareMatrix, fx: &[f64]) -> Vec<f64> {
+    let rhs: Vec<f64> = fx.iter().map(|v| -v).collect();
+
+    jac.solve(&rhs).unwrap_or_else(|_| {
+        (1..=jac.size)
+            .map(|j| (1..=jac.size).map(|i| -jac.get(i, j) * fx[i - 1]).sum())
+            .collect()
+    })
+}
+
+// Backtracking along p until the merit function |F|^2 / 2 decreases enough (Armijo condition)
+fn damped_step<F: Fn(&[f64]) -> Vec<f64>>(
+    f: &F,
+    x: &[f64],
+    fx: &[f64],
+    p: &[f64],
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    let merit = 0.5 * dot(fx, fx);
+    let mut lambda = 1.0;
+
+    while lambda >= 1e-10 {
+        let candidate: Vec<f64> = x.iter().zip(p).map(|(xi, pi)| xi + lambda * pi).collect();
+        let f_candidate = f(&candidate);
+
+        if 0.5 * dot(&f_candidate, &f_candidate) <= (1.0 - 2e-4 * lambda) * merit {
+            return Some((candidate, f_candidate));
+        }
+
+        lambda *= 0.5;
+    }
+
+    None
+}
+
+// Damped Newton-Raphson method for F(x) = 0 with a user supplied Jacobian.
+// Stops when every component of F is below tol.
+pub fn newton_system<F: Fn(&[f64]) -> Vec<f64>, J: Fn(&[f64]) -> SquareMatrix>(
+    f: F,
+    jac: J,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<SystemSolution, OptimizerError> {
+    let mut x = x0.to_vec();
+    let mut fx = f(&x);
+    if fx.len() != x.len() {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    loop {
+        if max_norm(&fx) <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+        if iterations == max_iterations {
+            break;
+        }
+
+        let p = newton_direction(&jac(&x), &fx);
+        match damped_step(&f, &x, &fx, &p) {
+            Some((x_next, f_next)) => {
+                x = x_next;
+                fx = f_next;
+            }
+            None => {
+                status = ConvergenceStatus::LineSearchFailed;
+                break;
+            }
+        }
 
-    while cur_position <= right {
-        max = f64::max(max, f(cur_position));
-        cur_position += precision;
+        iterations += 1;
     }
 
-    max
+    Ok(SystemSolution {
+        residual_norm: norm(&fx),
+        x,
+        fx,
+        iterations,
+        status,
+    })
+}
+
+// Damped Newton-Raphson method with a forward difference Jacobian
+pub fn fsolve<F: Fn(&[f64]) -> Vec<f64>>(
+    f: F,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<SystemSolution, OptimizerError> {
+    newton_system(&f, |x: &[f64]| jacobian(&f, x), x0, tol, max_iterations)
 }
 
-pub fn minimum<F: Fn(f64) -> f64>(f: F, left: f64, right: f64, precision: f64) -> f64 {
-    let mut min = f(left);
-    let mut cur_position = left;
+// Broyden's quasi-Newton method: the Jacobian is computed once, then kept up to date
+// with rank one updates. It is recomputed when the damped step fails.
+pub fn broyden<F: Fn(&[f64]) -> Vec<f64>>(
+    f: F,
+    x0: &[f64],
+    tol: f64,
+    max_iterations: usize,
+) -> Result<SystemSolution, OptimizerError> {
+    let n = x0.len();
+    let mut x = x0.to_vec();
+    let mut fx = f(&x);
+    if fx.len() != n {
+        return Err(OptimizerError::DimensionMismatch);
+    }
+
+    let mut b = jacobian(&f, &x);
+    let mut fresh = true;
+    let mut status = ConvergenceStatus::MaxIterations;
+    let mut iterations = 0;
+
+    loop {
+        if max_norm(&fx) <= tol {
+            status = ConvergenceStatus::Converged;
+            break;
+        }
+        if iterations == max_iterations {
+            break;
+        }
+
+        let p = newton_direction(&b, &fx);
+        let (x_next, f_next) = match damped_step(&f, &x, &fx, &p) {
+            Some(step) => step,
+            None if !fresh => {
+                b = jacobian(&f, &x);
+                fresh = true;
+                continue;
+            }
+            None => {
+                status = ConvergenceStatus::LineSearchFailed;
+                break;
+            }
+        };
+
+        iterations += 1;
 
-    while cur_position <= right {
-        min = f64::min(min, f(cur_position));
-        cur_position += precision;
+        // Rank one update B += (y - B s) s' / (s' s)
+        let s: Vec<f64> = (0..n).map(|i| x_next[i] - x[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| f_next[i] - fx[i]).collect();
+        let bs = b.mul_vec(&s);
+        let ss = dot(&s, &s);
+        if ss > 0.0 {
+            for i in 1..=n {
+                for j in 1..=n {
+                    b.set(i, j, b.get(i, j) + (y[i - 1] - bs[i - 1]) * s[j - 1] / ss);
+                }
+            }
+        }
+
+        fresh = false;
+        x = x_next;
+        fx = f_next;
     }
 
-    min
+    Ok(SystemSolution {
+        residual_norm: norm(&fx),
+        x,
+        fx,
+        iterations,
+        status,
+    })
+}
+
+// Outcome of a global optimizer
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct GlobalExtremum {
+    pub x: Vec<f64>,
+    pub fx: f64,
+    pub evaluations: usize,
 }
 
-pub fn stationary_points<F: Fn(f64) -> f64>(f: F, left: f64, right: f64, precision: f64) -> Vec<f64> {
-    let mut points: Vec<f64> = Vec::new();
-    let mut cur_position = left;
+// Objective with a budget of evaluations, remembering the best point seen inside the box.
+// Once the budget is spent every call returns NaN, which stops the local searches.
+struct Budget<'a, F: Fn(&[f64]) -> f64> {
+    f: F,
+    lower: &'a [f64],
+    upper: &'a [f64],
+    max_evaluations: usize,
+    evaluations: Cell<usize>,
+    best: RefCell<Option<(Vec<f64>, f64)>>,
+}
+
+impl<'a, F: Fn(&[f64]) -> f64> Budget<'a, F> {
+    fn new(
+        f: F,
+        lower: &'a [f64],
+        upper: &'a [f64],
+        max_evaluations: usize,
+    ) -> Result<Budget<'a, F>, OptimizerError> {
+        if lower.is_empty() || lower.len() != upper.len() {
+            return Err(OptimizerError::DimensionMismatch);
+        }
+        let valid = (0..lower.len())
+            .all(|j| lower[j].is_finite() && upper[j].is_finite() && lower[j] <= upper[j]);
+        if !valid {
+            return Err(OptimizerError::InvalidBounds);
+        }
+
+        Ok(Budget {
+            f,
+            lower,
+            upper,
+            max_evaluations,
+            evaluations: Cell::new(0),
+            best: RefCell::new(None),
+        })
+    }
+
+    fn eval(&self, x: &[f64]) -> f64 {
+        if self.exhausted() {
+            return f64::NAN;
+        }
+        self.evaluations.set(self.evaluations.get() + 1);
+
+        let fx = (self.f)(x);
+        let inside = (0..x.len()).all(|j| x[j] >= self.lower[j] && x[j] <= self.upper[j]);
+        let mut best = self.best.borrow_mut();
+        if inside && best.as_ref().map_or(!fx.is_nan(), |(_, fbest)| fx < *fbest) {
+            *best = Some((x.to_vec(), fx));
+        }
+
+        fx
+    }
+
+    fn exhausted(&self) -> bool {
+        self.evaluations.get() >= self.max_evaluations
+    }
+
+    // Uniformly distributed point in the box
+    fn random_point(&self, rng: &mut StdRng) -> Vec<f64> {
+        (0..self.lower.len())
+            .map(|j| self.lower[j] + rng.gen::<f64>() * (self.upper[j] - self.lower[j]))
+            .collect()
+    }
 
-    while cur_position < right {
-        let derivative = (differentiate(&f, cur_position) * 10000.0).round() / 10000.0;
-        if derivative == 0.0 {
-            points.push(cur_position);
+    fn result(self) -> Result<GlobalExtremum, OptimizerError> {
+        match self.best.into_inner() {
+            Some((x, fx)) => Ok(GlobalExtremum {
+                x,
+                fx,
+                evaluations: self.evaluations.get(),
+            }),
+            None => Err(OptimizerError::MaxIterations),
         }
-        cur_position += precision;
     }
+}
+
+// Differential evolution (DE/rand/1/bin) in the box [lower, upper].
+// Stops when the spread of the population values falls below tol or the budget runs out.
+pub fn differential_evolution<F: Fn(&[f64]) -> f64>(
+    f: F,
+    lower: &[f64],
+    upper: &[f64],
+    tol: f64,
+    max_evaluations: usize,
+    seed: u64,
+) -> Result<GlobalExtremum, OptimizerError> {
+    let budget = Budget::new(f, lower, upper, max_evaluations)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = lower.len();
+    let size = usize::max(10 * n, 20);
+    let weight = 0.8;
+    let crossover = 0.9;
+
+    let mut population: Vec<Vec<f64>> = (0..size).map(|_| budget.random_point(&mut rng)).collect();
+    let mut values: Vec<f64> = population.iter().map(|x| budget.eval(x)).collect();
+
+    'generations: while !budget.exhausted() {
+        for i in 0..size {
+            if budget.exhausted() {
+                break 'generations;
+            }
+
+            // Three distinct members, all different from i
+            let mut picks = [i; 3];
+            for k in 0..3 {
+                while picks[k] == i || picks[..k].contains(&picks[k]) {
+                    picks[k] = rng.gen_range(0, size);
+                }
+            }
+            let [a, b, c] = picks;
+
+            let forced = rng.gen_range(0, n);
+            let trial: Vec<f64> = (0..n)
+                .map(|j| {
+                    if j != forced && rng.gen::<f64>() >= crossover {
+                        return population[i][j];
+                    }
+                    let mutant = population[a][j] + weight * (population[b][j] - population[c][j]);
+                    if mutant < lower[j] || mutant > upper[j] {
+                        lower[j] + rng.gen::<f64>() * (upper[j] - lower[j])
+                    } else {
+                        mutant
+                    }
+                })
+                .collect();
+
+            let value = budget.eval(&trial);
+            if value <= values[i] || values[i].is_nan() {
+                population[i] = trial;
+                values[i] = value;
+            }
+        }
 
-    if (differentiate(&f, right) * 10000.0).round() / 10000.0 == 0.0 {
-        points.push(right);
+        let (low, high) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), &v| {
+                (low.min(v), high.max(v))
+            });
+        if high - low <= tol {
+            break;
+        }
     }
 
-    points
+    budget.result()
 }
 
-pub fn fzero<F: Fn(f64) -> f64>(f: F, left: f64, right: f64) -> f64 {
-    let precision = 2048;
+// Simulated annealing in the box [lower, upper], with geometric cooling over the whole budget
+pub fn simulated_annealing<F: Fn(&[f64]) -> f64>(
+    f: F,
+    lower: &[f64],
+    upper: &[f64],
+    max_evaluations: usize,
+    seed: u64,
+) -> Result<GlobalExtremum, OptimizerError> {
+    let budget = Budget::new(f, lower, upper, max_evaluations)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = lower.len();
+
+    // Initial temperature from the spread of a few random samples
+    let probes: Vec<f64> = (0..usize::min(10, max_evaluations / 10))
+        .map(|_| budget.eval(&budget.random_point(&mut rng)))
+        .filter(|v| v.is_finite())
+        .collect();
+    let mean = probes.iter().sum::<f64>() / probes.len() as f64;
+    let spread =
+        (probes.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / probes.len() as f64).sqrt();
+    let initial_temperature = if spread.is_finite() && spread > 0.0 {
+        spread
+    } else {
+        1.0
+    };
+
+    let mut x = budget.random_point(&mut rng);
+    let mut fx = budget.eval(&x);
+
+    while !budget.exhausted() {
+        let progress = budget.evaluations.get() as f64 / max_evaluations as f64;
+        let temperature = initial_temperature * 1e-8_f64.powf(progress);
+        // The neighbourhood shrinks with the temperature, from half the box down to 1e-4 of it
+        let radius = 0.5 * (temperature / initial_temperature).sqrt();
 
-    let step = (right - left) / precision as f64;
-    let mut samples: Vec<f64> = Vec::new();
-    let mut cur_position = left;
+        let candidate: Vec<f64> = (0..n)
+            .map(|j| {
+                let step = (2.0 * rng.gen::<f64>() - 1.0) * radius * (upper[j] - lower[j]);
+                (x[j] + step).clamp(lower[j], upper[j])
+            })
+            .collect();
+        let value = budget.eval(&candidate);
 
-    while cur_position < right {
-        samples.push(cur_position);
-        cur_position += step;
+        // Metropolis criterion
+        if value <= fx || fx.is_nan() || rng.gen::<f64>() < ((fx - value) / temperature).exp() {
+            x = candidate;
+            fx = value;
+        }
     }
 
-    let mut index = 0;
-    while index < precision - 2 {
-        if f(samples[index]).signum() != f(samples[index + 1]).signum() {
+    budget.result()
+}
+
+// L-BFGS local searches from random starting points in the box [lower, upper].
+// Only points inside the box are reported.
+pub fn multistart<F: Fn(&[f64]) -> f64>(
+    f: F,
+    lower: &[f64],
+    upper: &[f64],
+    starts: usize,
+    tol: f64,
+    max_evaluations: usize,
+    seed: u64,
+) -> Result<GlobalExtremum, OptimizerError> {
+    let budget = Budget::new(f, lower, upper, max_evaluations)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let objective = |x: &[f64]| -> f64 { budget.eval(x) };
+
+    for _start in 0..starts {
+        if budget.exhausted() {
             break;
         }
-        index += 1;
+        let x0 = budget.random_point(&mut rng);
+        // A start where the objective isn't finite is skipped
+        let _ = lbfgs(objective, &x0, tol, MAX_ITERATIONS, 10);
     }
 
-    let find_min_max = |a: f64, b: f64| -> (f64, f64) {
-        if f(a) < f(b) {
-            (a, b)
-        } else {
-            (b, a)
+    budget.result()
+}
+
+//  -------------------------------------------
+//  WASM ENTRY POINTS
+//  Objectives and constraints are JS callbacks
+//  (x) => f(x), x being a number or a Float64Array
+//  -------------------------------------------
+
+// Wraps a JS callback into a Rust function, remembering the first exception thrown
+pub(crate) fn js_function<'a>(
+    f: &'a js_sys::Function,
+    failure: &'a RefCell<Option<JsValue>>,
+) -> impl Fn(f64) -> f64 + 'a {
+    move |x: f64| -> f64 {
+        match f.call1(&JsValue::NULL, &JsValue::from_f64(x)) {
+            Ok(value) => value.as_f64().unwrap_or(f64::NAN),
+            Err(e) => {
+                failure.borrow_mut().get_or_insert(e);
+                f64::NAN
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub fn find_zeros(
+    f: &js_sys::Function,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<Vec<f64>, JsValue> {
+    let failure = RefCell::new(None);
+    let roots = fzeros(js_function(f, &failure), left, right, tol);
+
+    match failure.into_inner() {
+        Some(e) => Err(e),
+        None => Ok(roots),
+    }
+}
+
+// Stationary points of a JS callback as a JSON array of { X, Fx, Kind } objects
+#[wasm_bindgen]
+pub fn find_stationary_points(
+    f: &js_sys::Function,
+    left: f64,
+    right: f64,
+    precision: f64,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let points = stationary_points(js_function(f, &failure), left, right, precision);
+
+    if let Some(e) = failure.into_inner() {
+        return Err(e);
+    }
+    let points = points.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&points).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+fn js_extremum_output(
+    extremum: Result<Extremum, OptimizerError>,
+    failure: RefCell<Option<JsValue>>,
+) -> Result<String, JsValue> {
+    if let Some(e) = failure.into_inner() {
+        return Err(e);
+    }
+    let extremum = extremum.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&extremum).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Local minimum of a JS callback in [left, right] as a JSON { X, Fx, Iterations } object,
+// by Brent's method, or by golden-section search if `golden` is set
+#[wasm_bindgen]
+pub fn find_minimum(
+    f: &js_sys::Function,
+    left: f64,
+    right: f64,
+    tol: f64,
+    golden: bool,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let min = if golden {
+        golden_section(js_function(f, &failure), left, right, tol)
+    } else {
+        minimum(js_function(f, &failure), left, right, tol)
+    };
+
+    js_extremum_output(min, failure)
+}
+
+// Local maximum of a JS callback in [left, right] as a JSON { X, Fx, Iterations } object
+#[wasm_bindgen]
+pub fn find_maximum(
+    f: &js_sys::Function,
+    left: f64,
+    right: f64,
+    tol: f64,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let max = maximum(js_function(f, &failure), left, right, tol);
+
+    js_extremum_output(max, failure)
+}
+
+// Wraps a JS callback of several variables into a Rust function
+fn js_multivariate<'a>(
+    f: &'a js_sys::Function,
+    failure: &'a RefCell<Option<JsValue>>,
+) -> impl Fn(&[f64]) -> f64 + 'a {
+    move |x: &[f64]| -> f64 {
+        let point = js_sys::Float64Array::from(x);
+        match f.call1(&JsValue::NULL, &point) {
+            Ok(value) => value.as_f64().unwrap_or(f64::NAN),
+            Err(e) => {
+                failure.borrow_mut().get_or_insert(e);
+                f64::NAN
+            }
+        }
+    }
+}
+
+// Solves a JSON encoded LinearProgram, returning a JSON encoded LinearSolution
+#[wasm_bindgen]
+pub fn linear_program(problem: String) -> Result<String, JsValue> {
+    let problem: LinearProgram =
+        serde_json::from_str(&problem).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let solution = linprog(&problem).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&solution).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Nonlinear problem settings passed from JS as JSON
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct ConstrainedProblem {
+    x0: Vec<f64>,
+    // Missing or null bounds mean an unbounded variable
+    #[serde(default)]
+    lower: Vec<Option<f64>>,
+    #[serde(default)]
+    upper: Vec<Option<f64>>,
+    tol: f64,
+    max_iterations: usize,
+}
+
+// Result of a multivariate optimizer, serialized for JS
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct JsExtremum {
+    x: Vec<f64>,
+    fx: f64,
+    iterations: usize,
+    gradient_norm: f64,
+    converged: bool,
+}
+
+// Unconstrained multivariate minimizers
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+enum Method {
+    NelderMead,
+    Bfgs,
+    Lbfgs,
+}
+
+// Unconstrained problem settings passed from JS as JSON
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct UnconstrainedProblem {
+    x0: Vec<f64>,
+    tol: f64,
+    max_iterations: usize,
+    method: Method,
+    // Number of correction pairs kept by L-BFGS
+    #[serde(default = "default_memory")]
+    memory: usize,
+}
+
+fn default_memory() -> usize {
+    10
+}
+
+fn js_extremum(result: MultiExtremum) -> JsExtremum {
+    JsExtremum {
+        converged: result.status == ConvergenceStatus::Converged,
+        x: result.x,
+        fx: result.fx,
+        iterations: result.iterations,
+        gradient_norm: result.gradient_norm,
+    }
+}
+
+// Minimizes f from the starting point in `problem`, by Nelder-Mead, BFGS or L-BFGS
+#[wasm_bindgen]
+pub fn minimize(f: &js_sys::Function, problem: String) -> Result<String, JsValue> {
+    let problem: UnconstrainedProblem =
+        serde_json::from_str(&problem).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let failure = RefCell::new(None);
+    let objective = js_multivariate(f, &failure);
+    let (x0, tol, max_iterations) = (&problem.x0, problem.tol, problem.max_iterations);
+    let result = match problem.method {
+        Method::NelderMead => nelder_mead(objective, x0, tol, max_iterations),
+        Method::Bfgs => bfgs(objective, x0, tol, max_iterations),
+        Method::Lbfgs => lbfgs(objective, x0, tol, max_iterations, problem.memory),
+    };
+
+    if let Some(e) = failure.borrow_mut().take() {
+        return Err(e);
+    }
+    let result = result.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&js_extremum(result)).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Minimizes f subject to constraints g(x) <= 0 (an Array of callbacks) and the bounds in `problem`
+#[wasm_bindgen]
+pub fn minimize_constrained(
+    f: &js_sys::Function,
+    constraints: js_sys::Array,
+    problem: String,
+) -> Result<String, JsValue> {
+    let problem: ConstrainedProblem =
+        serde_json::from_str(&problem).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let n = problem.x0.len();
+
+    let bound = |bounds: &[Option<f64>], j: usize, default: f64| -> f64 {
+        bounds.get(j).copied().flatten().unwrap_or(default)
+    };
+    let lower: Vec<f64> = (0..n)
+        .map(|j| bound(&problem.lower, j, f64::NEG_INFINITY))
+        .collect();
+    let upper: Vec<f64> = (0..n)
+        .map(|j| bound(&problem.upper, j, f64::INFINITY))
+        .collect();
+
+    let failure = RefCell::new(None);
+    let callbacks: Vec<js_sys::Function> = constraints.iter().map(js_sys::Function::from).collect();
+    let wrapped: Vec<_> = callbacks
+        .iter()
+        .map(|c| js_multivariate(c, &failure))
+        .collect();
+    let references: Vec<Constraint> = wrapped.iter().map(|c| c as Constraint).collect();
+
+    let result = augmented_lagrangian(
+        js_multivariate(f, &failure),
+        &references,
+        &lower,
+        &upper,
+        &problem.x0,
+        problem.tol,
+        problem.max_iterations,
+    );
+
+    if let Some(e) = failure.borrow_mut().take() {
+        return Err(e);
+    }
+    let result = result.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&js_extremum(result)).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Curve fit serialized for JS
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct JsCurveFit {
+    parameters: Vec<f64>,
+    residuals: Vec<f64>,
+    covariance: Matrix,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    iterations: usize,
+    converged: bool,
+}
+
+// Fits model(x, parameters) to the data; confidence intervals are computed at the given level
+#[wasm_bindgen]
+pub fn fit_curve(
+    model: &js_sys::Function,
+    xdata: Vec<f64>,
+    ydata: Vec<f64>,
+    p0: Vec<f64>,
+    level: f64,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let wrapped = |x: f64, p: &[f64]| -> f64 {
+        let parameters = js_sys::Float64Array::from(p);
+        match model.call2(&JsValue::NULL, &JsValue::from_f64(x), &parameters) {
+            Ok(value) => value.as_f64().unwrap_or(f64::NAN),
+            Err(e) => {
+                failure.borrow_mut().get_or_insert(e);
+                f64::NAN
+            }
         }
     };
 
-    let min_max = find_min_max(left + step * index as f64, left + step * (index + 1) as f64);
-    let mut min = min_max.0;
-    let mut max = min_max.1;
+    let fit = curve_fit(wrapped, &xdata, &ydata, &p0, 1e-10, 200);
 
-    let mut mid = (max + min) * 0.5;
+    if let Some(e) = failure.borrow_mut().take() {
+        return Err(e);
+    }
+    let fit = fit.map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let (lower, upper) = fit.confidence_intervals(level).into_iter().unzip();
 
-    for _i in 0..(precision / 8) {
-        let midpoint = f(mid);
+    serde_json::to_string(&JsCurveFit {
+        converged: fit.status == ConvergenceStatus::Converged,
+        parameters: fit.parameters,
+        residuals: fit.residuals,
+        covariance: fit.covariance,
+        lower,
+        upper,
+        iterations: fit.iterations,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
 
-        if midpoint == 0.0 {
-            break;
-        } else if midpoint > 0.0 {
-            max = mid;
-        } else {
-            min = mid;
+// Wraps a JS callback returning an array of len numbers into a Rust vector function.
+// An exception or an array of another length is remembered, and the values become NaN
+fn js_vector<'a>(
+    f: &'a js_sys::Function,
+    len: usize,
+    failure: &'a RefCell<Option<JsValue>>,
+) -> impl Fn(&[f64]) -> Vec<f64> + 'a {
+    move |x: &[f64]| -> Vec<f64> {
+        let point = js_sys::Float64Array::from(x);
+        let error = match f.call1(&JsValue::NULL, &point) {
+            Ok(value) => {
+                let values = js_sys::Float64Array::new(&value).to_vec();
+                if values.len() == len {
+                    return values;
+                }
+                JsValue::from_str(&format!(
+                    "The function returned {} values instead of {}",
+                    values.len(),
+                    len
+                ))
+            }
+            Err(e) => e,
+        };
+        failure.borrow_mut().get_or_insert(error);
+        vec![f64::NAN; len]
+    }
+}
+
+// Solution of a system of equations, serialized for JS
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct JsSystemSolution {
+    x: Vec<f64>,
+    fx: Vec<f64>,
+    residual_norm: f64,
+    iterations: usize,
+    converged: bool,
+}
+
+fn js_system_output(
+    solution: Result<SystemSolution, OptimizerError>,
+    failure: &RefCell<Option<JsValue>>,
+) -> Result<String, JsValue> {
+    if let Some(e) = failure.borrow_mut().take() {
+        return Err(e);
+    }
+    let solution = solution.map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&JsSystemSolution {
+        converged: solution.status == ConvergenceStatus::Converged,
+        x: solution.x,
+        fx: solution.fx,
+        residual_norm: solution.residual_norm,
+        iterations: solution.iterations,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Solves F(x) = 0 with Newton's method. The optional Jacobian callback
+// returns the n x n matrix as a flat array, row by row.
+#[wasm_bindgen]
+pub fn solve_system(
+    f: &js_sys::Function,
+    jac: Option<js_sys::Function>,
+    x0: Vec<f64>,
+    tol: f64,
+    max_iterations: usize,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let n = x0.len();
+    let system = js_vector(f, n, &failure);
+
+    let solution = match &jac {
+        Some(jac) => {
+            let flat = js_vector(jac, n * n, &failure);
+            let matrix = |x: &[f64]| -> SquareMatrix {
+                let values = flat(x);
+                let mut m = SquareMatrix::new(n);
+                for i in 0..n {
+                    for j in 0..n {
+                        m.set(i + 1, j + 1, values[i * n + j]);
+                    }
+                }
+                m
+            };
+            newton_system(&system, matrix, &x0, tol, max_iterations)
         }
+        None => fsolve(&system, &x0, tol, max_iterations),
+    };
+
+    js_system_output(solution, &failure)
+}
+
+// Solves F(x) = 0 with Broyden's method
+#[wasm_bindgen]
+pub fn solve_system_broyden(
+    f: &js_sys::Function,
+    x0: Vec<f64>,
+    tol: f64,
+    max_iterations: usize,
+) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let solution = broyden(js_vector(f, x0.len(), &failure), &x0, tol, max_iterations);
+
+    js_system_output(solution, &failure)
+}
 
-        mid = (max + min) * 0.5;
+// Global optimization algorithms available from JS
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
+enum GlobalMethod {
+    DifferentialEvolution,
+    SimulatedAnnealing,
+    Multistart,
+}
+
+// Global optimization problem deserialized from JS
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct GlobalProblem {
+    method: GlobalMethod,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    max_evaluations: usize,
+    #[serde(default)]
+    seed: u64,
+    // Population spread for differential evolution, gradient norm for multistart
+    #[serde(default)]
+    tol: f64,
+    // Number of local searches for multistart
+    #[serde(default)]
+    starts: Option<usize>,
+}
+
+// Minimizes f in a box with the method chosen in the JSON `problem`, returning a JSON GlobalExtremum
+#[wasm_bindgen]
+pub fn minimize_global(f: &js_sys::Function, problem: String) -> Result<String, JsValue> {
+    let problem: GlobalProblem =
+        serde_json::from_str(&problem).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let failure = RefCell::new(None);
+    let objective = js_multivariate(f, &failure);
+    let (lower, upper) = (&problem.lower, &problem.upper);
+
+    let result = match problem.method {
+        GlobalMethod::DifferentialEvolution => differential_evolution(
+            objective,
+            lower,
+            upper,
+            problem.tol,
+            problem.max_evaluations,
+            problem.seed,
+        ),
+        GlobalMethod::SimulatedAnnealing => simulated_annealing(
+            objective,
+            lower,
+            upper,
+            problem.max_evaluations,
+            problem.seed,
+        ),
+        GlobalMethod::Multistart => multistart(
+            objective,
+            lower,
+            upper,
+            problem.starts.unwrap_or(usize::MAX),
+            problem.tol,
+            problem.max_evaluations,
+            problem.seed,
+        ),
+    };
+
+    if let Some(e) = failure.borrow_mut().take() {
+        return Err(e);
     }
+    let result = result.map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    mid
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
 #[cfg(test)]
@@ -102,62 +2341,546 @@ mod tests {
 
     #[test]
     fn maximum_test() {
-        let max = maximum(f64::cos, -1.0, 1.0, 0.0001);
+        let max = maximum(f64::cos, -1.0, 1.0, 1e-10).unwrap();
 
         // Approximate the result to 5 digits
-        let max = (max * 10000.0).round() / 10000.0;
+        let x = (max.x * 10000.0).round() / 10000.0;
+        let fx = (max.fx * 10000.0).round() / 10000.0;
 
         // Check if the result is correct
-        assert_eq!(max, 1.0);
+        assert_eq!(x, 0.0);
+        assert_eq!(fx, 1.0);
     }
 
     #[test]
     fn minimum_test() {
-        let min = minimum(f64::cos, 0.0, PI, 0.0001);
+        let min = minimum(f64::cos, 0.0, 2.0 * PI, 1e-10).unwrap();
 
         // Approximate the result to 5 digits
-        let min = (min * 10000.0).round() / 10000.0;
+        let x = (min.x * 10000.0).round() / 10000.0;
+        let fx = (min.fx * 10000.0).round() / 10000.0;
 
         // Check if the result is correct
-        assert_eq!(min, -1.0);
+        assert_eq!(x, (PI * 10000.0).round() / 10000.0);
+        assert_eq!(fx, -1.0);
     }
 
     #[test]
-    fn stationaryPoints_test() {
+    fn golden_section_test() {
+        let parabula = |x: f64| -> f64 { (x - 0.7) * (x - 0.7) + 2.0 };
+        let min = golden_section(parabula, -3.0, 5.0, 1e-10).unwrap();
 
-        let points = stationary_points(f64::cos, 0.0, 2.0 * PI, 0.0001);
+        assert!((min.x - 0.7).abs() < 1e-7);
+        assert!((min.fx - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn brent_minimize_test() {
+        let quartic = |x: f64| -> f64 { f64::powi(x - 1.5, 4) + (x - 1.5) * (x - 1.5) - 1.0 };
+        let min = brent_minimize(quartic, -3.0, 5.0, 1e-10).unwrap();
+
+        assert!((min.x - 1.5).abs() < 1e-7);
+        assert!((min.fx + 1.0).abs() < 1e-12);
+
+        // Parabolic steps converge faster than pure golden-section search
+        let golden = golden_section(quartic, -3.0, 5.0, 1e-10).unwrap();
+        assert!(min.iterations < golden.iterations);
+    }
+
+    #[test]
+    fn stationary_points_test() {
+        let points = stationary_points(f64::cos, 0.0, 2.0 * PI, 0.01).unwrap();
+        assert_eq!(points.len(), 3);
 
         // Maximum in 0
-        let point_0 = (points[0] * 10000.0).round() / 10000.0;
-        assert_eq!(point_0, 0.0);
+        assert!(points[0].x.abs() < 1e-10);
+        assert_eq!(points[0].kind, StationaryKind::Maximum);
 
         // Minimum in PI
-        let point_pi = (points[1] * 10000.0).round() / 10000.0;
-        let pi_approx = (PI * 10000.0).round() / 10000.0;
-        assert_eq!(point_pi, pi_approx);
+        assert!((points[1].x - PI).abs() < 1e-10);
+        assert_eq!(points[1].kind, StationaryKind::Minimum);
+        assert!((points[1].fx + 1.0).abs() < 1e-12);
 
         // Maximum in 2PI
-        let point_2pi = (points[2] * 10000.0).round() / 10000.0;
-        let twopi_approx = (2.0 * PI * 10000.0).round() / 10000.0;
-        assert_eq!(point_2pi, twopi_approx);
+        assert!((points[2].x - 2.0 * PI).abs() < 1e-10);
+        assert_eq!(points[2].kind, StationaryKind::Maximum);
+
+        // A flat minimum is a single point, even with a fine grid
+        let points = stationary_points(|x: f64| x.powi(4), -1.0, 1.0, 0.0001).unwrap();
+        assert_eq!(points.len(), 1);
+        assert!(points[0].x.abs() < 1e-3);
+        assert_eq!(points[0].kind, StationaryKind::Minimum);
+
+        // Saddle of x^3 in 0
+        let points = stationary_points(|x: f64| x.powi(3), -1.0, 1.0, 0.001).unwrap();
+        assert_eq!(points.len(), 1);
+        assert!(points[0].x.abs() < 1e-3);
+        assert_eq!(points[0].kind, StationaryKind::Inflection);
+
+        // x^3 - 3x has a maximum in -1 and a minimum in 1
+        let points = stationary_points(|x: f64| x.powi(3) - 3.0 * x, -3.0, 3.0, 0.01).unwrap();
+        assert_eq!(points.len(), 2);
+        assert!((points[0].x + 1.0).abs() < 1e-10);
+        assert_eq!(points[0].kind, StationaryKind::Maximum);
+        assert!((points[1].x - 1.0).abs() < 1e-10);
+        assert_eq!(points[1].kind, StationaryKind::Minimum);
+
+        // The step must be a positive number, small enough to sample with
+        for precision in [0.0, -0.1, f64::NAN, 1e-300] {
+            assert_eq!(
+                stationary_points(f64::cos, 0.0, 1.0, precision),
+                Err(OptimizerError::InvalidStep)
+            );
+        }
     }
 
     #[test]
     fn fzero_test() {
         // SINE FUNCTION
-        // Look for the first zero in the -3.0 - +3.0 range
-        let zero = fzero(f64::sin, -3.0, 3.0);
-        let sin_zero = f64::sin(zero);
-
-        // Approximate the result to 5 digits
-        let sin_zero = (sin_zero * 10000.0).round() / 10000.0;
+        // Look for the zero in the -3.0 - +3.0 range
+        let zero = fzero(f64::sin, -3.0, 3.0, 1e-12).unwrap();
 
-        assert_eq!(sin_zero, 0.0);
+        // Approximate the result to 10 digits
+        let x = (zero.x * 1e10).round() / 1e10;
+        assert_eq!(x, 0.0);
 
         // PARABULA
         let parabula = |x: f64| -> f64 { x * x - 3.0 * x + 1.0 };
-        let zero = fzero(parabula, 0.0, 1.0);
-        let parabula_zero = parabula(zero);
-        assert_eq!(parabula_zero, 0.0);
+        let zero = fzero(parabula, 0.0, 1.0, 1e-15).unwrap();
+        assert!((zero.x - (3.0 - f64::sqrt(5.0)) * 0.5).abs() < 1e-15);
+        assert!(zero.fx.abs() < 1e-15);
+
+        // Far fewer evaluations than bisection
+        assert!(zero.iterations < 15);
+
+        // No sign change in the interval
+        let no_bracket = fzero(parabula, 3.0, 4.0, 1e-12);
+        assert_eq!(no_bracket, Err(OptimizerError::NoBracket));
+    }
+
+    #[test]
+    fn fzeros_test() {
+        // Every zero of the sine function in the 0.0 - 10.0 range
+        let zeros = fzeros(f64::sin, 0.0, 10.0, 1e-12);
+        assert_eq!(zeros.len(), 4);
+        for (k, zero) in zeros.iter().enumerate() {
+            assert!((zero - k as f64 * PI).abs() < 1e-10);
+        }
+
+        // Tangential root, no sign change
+        let parabula = |x: f64| -> f64 { (x - 1.3) * (x - 1.3) };
+        let zeros = fzeros(parabula, 0.0, 3.0, 1e-12);
+        assert_eq!(zeros.len(), 1);
+        assert!((zeros[0] - 1.3).abs() < 1e-6);
+
+        // A minimum above zero is not a root
+        let lifted = |x: f64| -> f64 { (x - 1.3) * (x - 1.3) + 0.01 };
+        assert!(fzeros(lifted, 0.0, 3.0, 1e-12).is_empty());
+
+        // Endpoints are roots only where f vanishes
+        assert!(fzeros(|x: f64| x + 1e-9, 0.0, 1.0, 1e-12).is_empty());
+        assert_eq!(fzeros(|x: f64| x - 1.0, 0.0, 1.0, 1e-12), vec![1.0]);
+    }
+
+    // Rosenbrock's banana function, minimum in (1, 1)
+    fn rosenbrock(x: &[f64]) -> f64 {
+        100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2)
+    }
+
+    #[test]
+    fn nelder_mead_test() {
+        let min = nelder_mead(rosenbrock, &[-1.2, 1.0], 1e-8, 2000).unwrap();
+
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!((min.x[0] - 1.0).abs() < 1e-6);
+        assert!((min.x[1] - 1.0).abs() < 1e-6);
+        assert!(min.fx < 1e-12);
+
+        assert_eq!(
+            nelder_mead(rosenbrock, &[], 1e-8, 2000),
+            Err(OptimizerError::DimensionMismatch)
+        );
+        assert_eq!(
+            nelder_mead(|_: &[f64]| f64::INFINITY, &[0.0], 1e-8, 2000),
+            Err(OptimizerError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn bfgs_test() {
+        let min = bfgs(rosenbrock, &[-1.2, 1.0], 1e-6, 200).unwrap();
+
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!(min.gradient_norm <= 1e-6);
+        assert!((min.x[0] - 1.0).abs() < 1e-6);
+        assert!((min.x[1] - 1.0).abs() < 1e-6);
+
+        // Quadratic bowl
+        let bowl =
+            |x: &[f64]| -> f64 { (x[0] - 3.0).powi(2) + 10.0 * (x[1] + 2.0).powi(2) + x[2] * x[2] };
+        let min = bfgs(bowl, &[0.0, 0.0, 5.0], 1e-8, 200).unwrap();
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!((min.x[0] - 3.0).abs() < 1e-7);
+        assert!((min.x[1] + 2.0).abs() < 1e-7);
+        assert!(min.x[2].abs() < 1e-7);
+
+        assert_eq!(
+            bfgs(rosenbrock, &[], 1e-6, 200),
+            Err(OptimizerError::DimensionMismatch)
+        );
+        assert_eq!(
+            bfgs(|x: &[f64]| x[0].ln(), &[-1.0], 1e-6, 200),
+            Err(OptimizerError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn lbfgs_test() {
+        let min = lbfgs(rosenbrock, &[-1.2, 1.0], 1e-6, 200, 5).unwrap();
+
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!((min.x[0] - 1.0).abs() < 1e-6);
+        assert!((min.x[1] - 1.0).abs() < 1e-6);
+
+        // Not enough iterations
+        let min = lbfgs(rosenbrock, &[-1.2, 1.0], 1e-6, 3, 5).unwrap();
+        assert_eq!(min.status, ConvergenceStatus::MaxIterations);
+        assert_eq!(min.iterations, 3);
+
+        // No memory behaves like a memory of one pair
+        let min = lbfgs(rosenbrock, &[-1.2, 1.0], 1e-6, 500, 0).unwrap();
+        assert_eq!(
+            Ok(min.clone()),
+            lbfgs(rosenbrock, &[-1.2, 1.0], 1e-6, 500, 1)
+        );
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+
+        assert_eq!(
+            lbfgs(rosenbrock, &[], 1e-6, 200, 5),
+            Err(OptimizerError::DimensionMismatch)
+        );
+        assert_eq!(
+            lbfgs(|_: &[f64]| f64::NAN, &[1.0, 2.0], 1e-6, 200, 5),
+            Err(OptimizerError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn augmented_lagrangian_test() {
+        // Closest point to (2, 1) in the half plane x + y <= 2
+        let distance = |x: &[f64]| -> f64 { (x[0] - 2.0).powi(2) + (x[1] - 1.0).powi(2) };
+        let half_plane = |x: &[f64]| -> f64 { x[0] + x[1] - 2.0 };
+        let free = [f64::NEG_INFINITY, f64::NEG_INFINITY];
+        let none = [f64::INFINITY, f64::INFINITY];
+
+        let min = augmented_lagrangian(
+            distance,
+            &[&half_plane],
+            &free,
+            &none,
+            &[0.0, 0.0],
+            1e-8,
+            50,
+        )
+        .unwrap();
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!((min.x[0] - 1.5).abs() < 1e-5);
+        assert!((min.x[1] - 0.5).abs() < 1e-5);
+
+        // Bound-constrained Rosenbrock: the minimum moves to the boundary x = 0.5
+        let min = augmented_lagrangian(
+            rosenbrock,
+            &[],
+            &[-2.0, -2.0],
+            &[0.5, 2.0],
+            &[-1.2, 1.0],
+            1e-8,
+            50,
+        )
+        .unwrap();
+        assert_eq!(min.status, ConvergenceStatus::Converged);
+        assert!((min.x[0] - 0.5).abs() < 1e-5);
+        assert!((min.x[1] - 0.25).abs() < 1e-5);
+
+        // Mismatched bounds
+        let wrong = augmented_lagrangian(rosenbrock, &[], &[0.0], &[1.0], &[0.0, 0.0], 1e-8, 50);
+        assert_eq!(wrong, Err(OptimizerError::DimensionMismatch));
+    }
+
+    fn matrix(rows: usize, cols: usize, values: &[f64]) -> Matrix {
+        let mut mat = Matrix::new(cols, rows);
+        for i in 1..=rows {
+            for j in 1..=cols {
+                mat.set(i, j, values[(i - 1) * cols + (j - 1)]);
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn linprog_test() {
+        // Maximize 3x + 5y with x <= 4, 2y <= 12, 3x + 2y <= 18
+        let problem = LinearProgram {
+            objective: vec![-3.0, -5.0],
+            inequality_matrix: Some(matrix(3, 2, &[1.0, 0.0, 0.0, 2.0, 3.0, 2.0])),
+            inequality_vector: vec![4.0, 12.0, 18.0],
+            equality_matrix: None,
+            equality_vector: vec![],
+        };
+        let solution = linprog(&problem).unwrap();
+        assert!((solution.x[0] - 2.0).abs() < 1e-9);
+        assert!((solution.x[1] - 6.0).abs() < 1e-9);
+        assert!((solution.fx + 36.0).abs() < 1e-9);
+
+        // Equality and "greater than" constraints: x + 2y = 4, x + y >= 3
+        let problem = LinearProgram {
+            objective: vec![1.0, 1.0],
+            inequality_matrix: Some(matrix(1, 2, &[-1.0, -1.0])),
+            inequality_vector: vec![-3.0],
+            equality_matrix: Some(matrix(1, 2, &[1.0, 2.0])),
+            equality_vector: vec![4.0],
+        };
+        let solution = linprog(&problem).unwrap();
+        assert!((solution.x[0] - 2.0).abs() < 1e-9);
+        assert!((solution.x[1] - 1.0).abs() < 1e-9);
+
+        // Same problem passed as JSON, like from JS
+        let json = serde_json::to_string(&problem).unwrap();
+        let solution: LinearSolution =
+            serde_json::from_str(&linear_program(json).unwrap()).unwrap();
+        assert!((solution.fx - 3.0).abs() < 1e-9);
+
+        // x <= 1 and x >= 2
+        let infeasible = LinearProgram {
+            objective: vec![1.0],
+            inequality_matrix: Some(matrix(2, 1, &[1.0, -1.0])),
+            inequality_vector: vec![1.0, -2.0],
+            equality_matrix: None,
+            equality_vector: vec![],
+        };
+        assert_eq!(linprog(&infeasible), Err(OptimizerError::Infeasible));
+
+        // Minimize -x with x - y <= 1
+        let unbounded = LinearProgram {
+            objective: vec![-1.0, 0.0],
+            inequality_matrix: Some(matrix(1, 2, &[1.0, -1.0])),
+            inequality_vector: vec![1.0],
+            equality_matrix: None,
+            equality_vector: vec![],
+        };
+        assert_eq!(linprog(&unbounded), Err(OptimizerError::Unbounded));
+
+        // Matrix from JS whose entries don't match its sizes
+        let malformed: LinearProgram = serde_json::from_str(
+            r#"{"Objective":[1,1],"InequalityMatrix":{"Sizex":2,"Sizey":2,"Matrix":[1,0,0]},"InequalityVector":[1,1]}"#,
+        )
+        .unwrap();
+        assert_eq!(linprog(&malformed), Err(OptimizerError::DimensionMismatch));
+    }
+
+    #[test]
+    fn fsolve_test() {
+        // Intersection of the circle x^2 + y^2 = 4 and the hyperbola xy = 1
+        let f =
+            |x: &[f64]| -> Vec<f64> { vec![x[0] * x[0] + x[1] * x[1] - 4.0, x[0] * x[1] - 1.0] };
+        let solution = fsolve(f, &[2.0, 0.0], 1e-12, 50).unwrap();
+
+        assert_eq!(solution.status, ConvergenceStatus::Converged);
+        assert!(solution.residual_norm <= 1e-12);
+
+        // Exact solution: x = sqrt(2 + sqrt(3)), y = 1 / x
+        assert_eq!((solution.x[0] * 10000.0).round() / 10000.0, 1.9319);
+        assert_eq!((solution.x[1] * 10000.0).round() / 10000.0, 0.5176);
+
+        // Analytic Jacobian
+        let jac = |x: &[f64]| -> SquareMatrix {
+            let mut m = SquareMatrix::new(2);
+            m.set(1, 1, 2.0 * x[0]);
+            m.set(1, 2, 2.0 * x[1]);
+            m.set(2, 1, x[1]);
+            m.set(2, 2, x[0]);
+            m
+        };
+        let exact = newton_system(f, jac, &[2.0, 0.0], 1e-12, 50).unwrap();
+        assert!((exact.x[0] - solution.x[0]).abs() < 1e-10);
+        assert!((exact.x[1] - solution.x[1]).abs() < 1e-10);
+
+        // Root of the Rosenbrock system from the classic starting point,
+        // where the full Newton step overshoots
+        let rosenbrock_system =
+            |x: &[f64]| -> Vec<f64> { vec![10.0 * (x[1] - x[0] * x[0]), 1.0 - x[0]] };
+        let solution = fsolve(rosenbrock_system, &[-1.2, 1.0], 1e-10, 100).unwrap();
+        assert_eq!(solution.status, ConvergenceStatus::Converged);
+        assert!((solution.x[0] - 1.0).abs() < 1e-9);
+        assert!((solution.x[1] - 1.0).abs() < 1e-9);
+
+        assert_eq!(
+            fsolve(|x: &[f64]| vec![x[0]], &[1.0, 2.0], 1e-10, 10),
+            Err(OptimizerError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn broyden_test() {
+        let f =
+            |x: &[f64]| -> Vec<f64> { vec![x[0] * x[0] + x[1] * x[1] - 4.0, x[0] * x[1] - 1.0] };
+        let solution = broyden(f, &[2.0, 0.0], 1e-12, 100).unwrap();
+
+        assert_eq!(solution.status, ConvergenceStatus::Converged);
+        assert_eq!((solution.x[0] * 10000.0).round() / 10000.0, 1.9319);
+        assert_eq!((solution.x[1] * 10000.0).round() / 10000.0, 0.5176);
+
+        // Three equations: chemical-equilibrium-like system with root (1, 2, 3)
+        let g = |x: &[f64]| -> Vec<f64> {
+            vec![
+                x[0] * x[1] - 2.0,
+                x[1] * x[2] - 6.0,
+                x[0] + x[1] + x[2] - 6.0,
+            ]
+        };
+        let solution = broyden(g, &[1.2, 1.8, 3.1], 1e-12, 100).unwrap();
+        assert_eq!(solution.status, ConvergenceStatus::Converged);
+        for (i, xi) in solution.x.iter().enumerate() {
+            assert!((xi - (i + 1) as f64).abs() < 1e-9);
+        }
+    }
+
+    // Many local minima, global minimum 0 in the origin
+    fn rastrigin(x: &[f64]) -> f64 {
+        10.0 * x.len() as f64
+            + x.iter()
+                .map(|xi| xi * xi - 10.0 * (2.0 * PI * xi).cos())
+                .sum::<f64>()
+    }
+
+    #[test]
+    fn differential_evolution_test() {
+        let lower = [-5.12, -5.12];
+        let upper = [5.12, 5.12];
+        let result = differential_evolution(rastrigin, &lower, &upper, 1e-12, 20000, 7).unwrap();
+
+        assert!(result.fx < 1e-8);
+        assert!(result.x.iter().all(|xi| xi.abs() < 1e-4));
+        assert!(result.evaluations <= 20000);
+
+        // Same seed, same result
+        let again = differential_evolution(rastrigin, &lower, &upper, 1e-12, 20000, 7).unwrap();
+        assert_eq!(result, again);
+
+        // The budget is respected
+        let short = differential_evolution(rastrigin, &lower, &upper, 0.0, 100, 7).unwrap();
+        assert_eq!(short.evaluations, 100);
+
+        assert_eq!(
+            differential_evolution(rastrigin, &[1.0], &[0.0], 1e-8, 100, 7),
+            Err(OptimizerError::InvalidBounds)
+        );
+        assert_eq!(
+            differential_evolution(rastrigin, &[0.0, 0.0], &[1.0], 1e-8, 100, 7),
+            Err(OptimizerError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn simulated_annealing_test() {
+        let lower = [-5.12, -5.12];
+        let upper = [5.12, 5.12];
+        let result = simulated_annealing(rastrigin, &lower, &upper, 50000, 11).unwrap();
+
+        // Global minimum in the origin
+        assert!(result.fx < 1e-3);
+        assert!(result.x.iter().all(|xi| xi.abs() < 1e-2));
+        assert_eq!(result.evaluations, 50000);
+
+        // Same seed, same result
+        let again = simulated_annealing(rastrigin, &lower, &upper, 50000, 11).unwrap();
+        assert_eq!(result, again);
+    }
+
+    #[test]
+    fn multistart_test() {
+        // Local minima of x sin(x) in [0, 20] get deeper to the right:
+        // the global one is close to x = 17.336
+        let f = |x: &[f64]| -> f64 { x[0] * x[0].sin() };
+        let result = multistart(f, &[0.0], &[20.0], 20, 1e-10, 5000, 3).unwrap();
+
+        assert_eq!((result.x[0] * 1000.0).round() / 1000.0, 17.336);
+        assert!(result.evaluations <= 5000);
+
+        // Rastrigin needs many starts, but one of them falls in the right basin
+        let result = multistart(
+            rastrigin,
+            &[-5.12, -5.12],
+            &[5.12, 5.12],
+            200,
+            1e-10,
+            50000,
+            3,
+        )
+        .unwrap();
+        assert!(result.fx < 1e-8);
+    }
+
+    #[test]
+    fn curve_fit_test() {
+        // Exponential decay with a small deterministic disturbance
+        let decay = |x: f64, p: &[f64]| -> f64 { p[0] * f64::exp(-p[1] * x) };
+        let xdata: Vec<f64> = (0..20).map(|i| i as f64 * 0.5).collect();
+        let ydata: Vec<f64> = xdata
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| 3.0 * f64::exp(-0.7 * x) + 0.01 * f64::sin(7.0 * i as f64))
+            .collect();
+
+        let fit = curve_fit(decay, &xdata, &ydata, &[1.0, 0.1], 1e-12, 200).unwrap();
+        assert_eq!(fit.status, ConvergenceStatus::Converged);
+        assert!((fit.parameters[0] - 3.0).abs() < 0.02);
+        assert!((fit.parameters[1] - 0.7).abs() < 0.01);
+        assert_eq!(fit.degrees_of_freedom, 18);
+        assert_eq!(fit.residuals.len(), 20);
+
+        // Symmetric covariance with positive variances
+        assert!((fit.covariance.get(1, 2) - fit.covariance.get(2, 1)).abs() < 1e-15);
+        assert!(fit.covariance.get(1, 1) > 0.0 && fit.covariance.get(2, 2) > 0.0);
+
+        // The intervals contain the true parameters and widen with the level
+        let ci95 = fit.confidence_intervals(0.95);
+        let ci99 = fit.confidence_intervals(0.99);
+        assert!(ci95[0].0 < 3.0 && 3.0 < ci95[0].1);
+        assert!(ci95[1].0 < 0.7 && 0.7 < ci95[1].1);
+        assert!(ci99[1].1 - ci99[1].0 > ci95[1].1 - ci95[1].0);
+
+        // Exact logistic data is fitted exactly
+        let logistic = |x: f64, p: &[f64]| -> f64 { p[0] / (1.0 + f64::exp(-p[1] * (x - p[2]))) };
+        let xdata: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let ydata: Vec<f64> = xdata
+            .iter()
+            .map(|&x| logistic(x, &[10.0, 0.4, 12.0]))
+            .collect();
+        let fit = curve_fit(logistic, &xdata, &ydata, &[5.0, 1.0, 10.0], 1e-12, 200).unwrap();
+        assert!((fit.parameters[0] - 10.0).abs() < 1e-6);
+        assert!((fit.parameters[1] - 0.4).abs() < 1e-6);
+        assert!((fit.parameters[2] - 12.0).abs() < 1e-6);
+
+        // Not enough data points
+        let few = curve_fit(
+            logistic,
+            &[1.0, 2.0],
+            &[1.0, 2.0],
+            &[5.0, 1.0, 10.0],
+            1e-12,
+            200,
+        );
+        assert_eq!(few, Err(OptimizerError::DimensionMismatch));
+
+        // NaN from the model
+        let undefined = |_: f64, _: &[f64]| -> f64 { f64::NAN };
+        let fit = curve_fit(undefined, &xdata, &ydata, &[1.0], 1e-12, 200);
+        assert_eq!(fit, Err(OptimizerError::NotFinite));
+
+        // A kink the forward differences can't see past: every step is uphill
+        let ramp = |_: f64, p: &[f64]| -> f64 { p[0].max(0.0) };
+        let fit = curve_fit(ramp, &xdata, &vec![-1.0; 30], &[0.0], 1e-12, 200).unwrap();
+        assert_eq!(fit.status, ConvergenceStatus::Stalled);
     }
 }
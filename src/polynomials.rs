@@ -0,0 +1,331 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::f64::consts::*;
+use std::ops::{Add, Div, Mul, Sub};
+use wasm_bindgen::prelude::*;
+
+/**
+ *  ------------------------------------
+ *  COMPLEX NUMBERS
+ *  Just enough arithmetic for root finding
+ *  ------------------------------------
+ */
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn abs(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    fn div(self, other: Complex) -> Complex {
+        let den = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / den,
+            (self.im * other.re - self.re * other.im) / den,
+        )
+    }
+}
+
+/**
+ *  ---------------------------------------------------
+ *  POLYNOMIALS
+ *  Coefficients in descending powers, like MATLAB's polyval:
+ *  [1, -3, 2] is x^2 - 3x + 2
+ *  ---------------------------------------------------
+ */
+
+#[wasm_bindgen]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Polynomial {
+    coefficients: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Polynomial {
+    #[wasm_bindgen(constructor)]
+    pub fn new(coefficients: Vec<f64>) -> Polynomial {
+        // Leading zeros don't change the polynomial
+        let first = coefficients
+            .iter()
+            .position(|&c| c != 0.0)
+            .unwrap_or(coefficients.len());
+
+        Polynomial {
+            coefficients: coefficients[first..].to_vec(),
+        }
+    }
+
+    pub fn coefficients(&self) -> Vec<f64> {
+        self.coefficients.clone()
+    }
+
+    // Degree of the zero polynomial is reported as 0
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    // Horner's scheme
+    pub fn eval(&self, x: f64) -> f64 {
+        self.coefficients.iter().fold(0.0, |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Polynomial {
+        let n = self.degree();
+        let coefficients = self.coefficients[..n]
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| c * (n - i) as f64)
+            .collect();
+
+        Polynomial::new(coefficients)
+    }
+
+    // All the roots as a JSON array of { Re, Im } objects
+    #[wasm_bindgen(js_name = roots)]
+    pub fn roots_json(&self) -> String {
+        serde_json::to_string(&self.roots()).expect("Complex numbers are always serializable")
+    }
+
+    // Real roots only, in ascending order
+    pub fn real_roots(&self) -> Vec<f64> {
+        self.roots()
+            .iter()
+            .filter(|z| z.im == 0.0)
+            .map(|z| z.re)
+            .collect()
+    }
+}
+
+impl Polynomial {
+    fn eval_complex(&self, z: Complex) -> Complex {
+        self.coefficients
+            .iter()
+            .fold(Complex::new(0.0, 0.0), |acc, &c| {
+                acc * z + Complex::new(c, 0.0)
+            })
+    }
+
+    // Bound on the rounding error of Horner's scheme at x
+    fn rounding_error(&self, x: f64) -> f64 {
+        let magnitude = self
+            .coefficients
+            .iter()
+            .fold(0.0, |acc, &c| acc * x.abs() + c.abs());
+        4.0 * self.coefficients.len() as f64 * f64::EPSILON * magnitude
+    }
+
+    // All the complex roots, through the Aberth-Ehrlich simultaneous iteration
+    pub fn roots(&self) -> Vec<Complex> {
+        // Trailing zeros are roots at the origin
+        let zeros_at_origin = self
+            .coefficients
+            .iter()
+            .rev()
+            .take_while(|&&c| c == 0.0)
+            .count();
+        let reduced = Polynomial::new(
+            self.coefficients[..self.coefficients.len() - zeros_at_origin].to_vec(),
+        );
+        let derivative = reduced.derivative();
+        let n = reduced.degree();
+
+        let mut roots: Vec<Complex> = vec![Complex::new(0.0, 0.0); zeros_at_origin];
+
+        if n == 0 {
+            return roots;
+        }
+
+        // Initial guesses on a circle of radius given by the geometric mean of the roots,
+        // with an angular offset to avoid symmetric stalls
+        let radius = (reduced.coefficients[n] / reduced.coefficients[0])
+            .abs()
+            .powf(1.0 / n as f64);
+        let mut z: Vec<Complex> = (0..n)
+            .map(|k| {
+                let angle = 2.0 * PI * k as f64 / n as f64 + 0.4;
+                Complex::new(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+
+        for _iteration in 0..500 {
+            let mut converged = true;
+
+            for k in 0..n {
+                let p = reduced.eval_complex(z[k]);
+                if p.abs() == 0.0 {
+                    continue;
+                }
+
+                let ratio = p / derivative.eval_complex(z[k]);
+                let repulsion = (0..n)
+                    .filter(|&j| j != k)
+                    .fold(Complex::new(0.0, 0.0), |acc, j| {
+                        acc + Complex::new(1.0, 0.0) / (z[k] - z[j])
+                    });
+                let correction = ratio / (Complex::new(1.0, 0.0) - ratio * repulsion);
+
+                if !(correction.re.is_finite() && correction.im.is_finite()) {
+                    continue;
+                }
+
+                z[k] = z[k] - correction;
+
+                if correction.abs() > 4.0 * f64::EPSILON * f64::max(z[k].abs(), 1.0) {
+                    converged = false;
+                }
+            }
+
+            if converged {
+                break;
+            }
+        }
+
+        // Snap numerically real roots onto the real axis. A root of multiplicity m is only
+        // resolved to about eps^(1/m), so the test is on the residual at its real part,
+        // against the rounding error of evaluating the polynomial there
+        for root in z.iter_mut() {
+            if root.im.abs() <= 1e-10 * f64::max(root.abs(), 1.0)
+                || reduced.eval(root.re).abs() <= reduced.rounding_error(root.re)
+            {
+                root.im = 0.0;
+            }
+        }
+
+        roots.append(&mut z);
+        roots.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn eval_test() {
+        let p = Polynomial::new(vec![0.0, 2.0, -3.0, 1.0]);
+
+        assert_eq!(p.degree(), 2);
+        assert_eq!(p.eval(3.0), 10.0);
+        assert_eq!(p.eval(0.0), 1.0);
+    }
+
+    #[test]
+    fn derivative_test() {
+        let p = Polynomial::new(vec![1.0, 0.0, -2.0, 5.0]);
+
+        assert_eq!(p.derivative().coefficients(), vec![3.0, 0.0, -2.0]);
+        assert_eq!(p.derivative().derivative().coefficients(), vec![6.0, 0.0]);
+    }
+
+    #[test]
+    fn roots_test() {
+        // Real roots
+        let p = Polynomial::new(vec![1.0, -3.0, 2.0]);
+        let roots = p.real_roots();
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] - 1.0).abs() < 1e-12);
+        assert!((roots[1] - 2.0).abs() < 1e-12);
+
+        // Complex conjugate roots of x^2 + 1
+        let p = Polynomial::new(vec![1.0, 0.0, 1.0]);
+        let roots = p.roots();
+        assert!(p.real_roots().is_empty());
+        assert!(roots[0].re.abs() < 1e-12 && (roots[0].im + 1.0).abs() < 1e-12);
+        assert!(roots[1].re.abs() < 1e-12 && (roots[1].im - 1.0).abs() < 1e-12);
+
+        // Roots at the origin and mixed roots: x (x - 4) (x^2 + 2x + 5)
+        let p = Polynomial::new(vec![1.0, -2.0, -3.0, -20.0, 0.0]);
+        let roots = p.roots();
+        assert_eq!(roots.len(), 4);
+        assert!((roots[0] - Complex::new(-1.0, -2.0)).abs() < 1e-12);
+        assert!((roots[1] - Complex::new(-1.0, 2.0)).abs() < 1e-12);
+        assert_eq!(roots[3].im, 0.0);
+        assert!((roots[3].re - 4.0).abs() < 1e-12);
+        assert_eq!(roots[2], Complex::new(0.0, 0.0));
+
+        // Wilkinson-like polynomial with roots 1..6
+        let mut coefficients = vec![1.0];
+        for r in 1..=6 {
+            let mut next = coefficients.clone();
+            next.push(0.0);
+            for i in 1..next.len() {
+                next[i] -= r as f64 * coefficients[i - 1];
+            }
+            coefficients = next;
+        }
+        let roots = Polynomial::new(coefficients).real_roots();
+        assert_eq!(roots.len(), 6);
+        for (i, root) in roots.iter().enumerate() {
+            assert!((root - (i + 1) as f64).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn multiple_roots_test() {
+        // (x - 1)^2
+        let roots = Polynomial::new(vec![1.0, -2.0, 1.0]).real_roots();
+        assert_eq!(roots.len(), 2);
+        assert!(roots.iter().all(|r| (r - 1.0).abs() < 1e-7));
+
+        // (x - 1)^3 (x + 2)
+        let roots = Polynomial::new(vec![1.0, -1.0, -3.0, 5.0, -2.0]).real_roots();
+        assert_eq!(roots.len(), 4);
+        assert!((roots[0] + 2.0).abs() < 1e-12);
+        assert!(roots[1..].iter().all(|r| (r - 1.0).abs() < 1e-4));
+
+        // Complex double roots stay complex: (x^2 + 1)^2
+        assert!(Polynomial::new(vec![1.0, 0.0, 2.0, 0.0, 1.0])
+            .real_roots()
+            .is_empty());
+
+        // NaN coefficients don't panic
+        assert!(Polynomial::new(vec![1.0, f64::NAN, 1.0])
+            .real_roots()
+            .is_empty());
+    }
+}
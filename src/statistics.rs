@@ -1,13 +1,22 @@
 use crate::calculus::integrate;
-use crate::optimizers::fzero;
+use crate::optimizers::{fzero, js_function};
+use crate::{MatrixTraits, SquareMatrix};
+use rand::distributions::Open01;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::RefCell;
 use std::f64::consts::*;
+use std::io::{Error, ErrorKind};
 use wasm_bindgen::prelude::*;
 
 /**
  *  ------------------------------------------------------
  *  NORMAL DISTRIBUTION
  *  Gaussian distribitions (normalized and non-normalized)
- *  Density, Cumulate and Quantile functions
+ *  Error function, its complement and its inverse
+ *  Density, Cumulate and Quantile functions (also in log space)
  *  ------------------------------------------------------
  */
 
@@ -15,7 +24,7 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 pub fn normpdf(x: f64, mu: f64, sigma: f64) -> f64 {
     // f64::exp(-0.5 * f64::powi((x - mu) / sigma, 2)) / (sigma * f64::sqrt(2.0 * PI))
-    s_normpdf(x - mu) / sigma
+    s_normpdf((x - mu) / sigma) / sigma
 }
 
 // Normal distribution
@@ -24,6 +33,62 @@ pub fn s_normpdf(x: f64) -> f64 {
     f64::exp(-0.5 * f64::powi(x, 2)) / f64::sqrt(2.0 * PI)
 }
 
+// Error function, through the regularized incomplete gamma: erf(x) = P(1/2, x^2)
+#[wasm_bindgen]
+pub fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x < 0.0 {
+        -erf(-x)
+    } else if x < 1e-8 {
+        // x^2 would lose the leading term
+        2.0 * x / PI.sqrt()
+    } else {
+        regincgamma(0.5, x * x)
+    }
+}
+
+// Complementary error function erfc(x) = 1 - erf(x) = Q(1/2, x^2), accurate in the right tail
+#[wasm_bindgen]
+pub fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x < 0.0 {
+        1.0 + erf(-x)
+    } else {
+        reguppincgamma(0.5, x * x)
+    }
+}
+
+// Inverse error function
+#[wasm_bindgen]
+pub fn erfinv(y: f64) -> f64 {
+    if y.is_nan() || !(-1.0..=1.0).contains(&y) {
+        return f64::NAN;
+    } else if y == 0.0 {
+        return y;
+    } else if y.abs() == 1.0 {
+        return y * f64::INFINITY;
+    }
+
+    let a = y.abs();
+    let mut x = -s_norminv(0.5 * (1.0 - a)) / SQRT_2;
+
+    // One Halley step on erf(x) = a; near 1 the residual is computed through erfc,
+    // since 1 - a is exact there
+    let residual = if a > 0.5 {
+        (1.0 - a) - erfc(x)
+    } else {
+        erf(x) - a
+    };
+    let derivative = 2.0 / PI.sqrt() * (-x * x).exp();
+    if derivative > 0.0 {
+        x -= residual / (derivative + x * residual);
+    }
+
+    x.copysign(y)
+}
+
 // Gaussian cumulate distribution
 #[wasm_bindgen]
 pub fn normcdf(x: f64, mu: f64, sigma: f64) -> f64 {
@@ -33,25 +98,134 @@ pub fn normcdf(x: f64, mu: f64, sigma: f64) -> f64 {
 // Standard cumulate distribution
 #[wasm_bindgen]
 pub fn s_normcdf(x: f64) -> f64 {
-    // Using the Zelen & Severo's approximate algorithm
-    let t: f64 = 1.0 / (1.0 + 0.2316419 * x);
-    1.0 - s_normpdf(x)
-        * (0.319381530 * t - 0.356563782 * f64::powi(t, 2) + 1.781477937 * f64::powi(t, 3)
-            - 1.821255978 * f64::powi(t, 4)
-            + 1.330274429 * f64::powi(t, 5))
+    0.5 * erfc(-x / SQRT_2)
+}
+
+// Logarithm of the Gaussian cumulate distribution
+#[wasm_bindgen]
+pub fn lognormcdf(x: f64, mu: f64, sigma: f64) -> f64 {
+    s_lognormcdf((x - mu) / sigma)
+}
+
+// Logarithm of the standard cumulate distribution, finite even where the cdf underflows
+#[wasm_bindgen]
+pub fn s_lognormcdf(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x < -5.0 {
+        // Phi(x) = Q(1/2, x^2 / 2) / 2
+        ln_incgamma_fraction(0.5, 0.5 * x * x) - LN_2
+    } else if x > 0.0 {
+        (-s_normcdf(-x)).ln_1p()
+    } else {
+        s_normcdf(x).ln()
+    }
+}
+
+// Logarithm of the Gaussian distribution
+#[wasm_bindgen]
+pub fn lognormpdf(x: f64, mu: f64, sigma: f64) -> f64 {
+    let z = (x - mu) / sigma;
+    -0.5 * z * z - sigma.ln() - 0.5 * (2.0 * PI).ln()
 }
 
 // Standard distribution quantile
 #[wasm_bindgen]
 pub fn s_norminv(x: f64) -> f64 {
-    // Using Shore's approximate algorithm
-    if x < 0.5 {
-        -5.5556 * (1.0 - f64::powf(x / (1.0 - x), 0.1186))
+    // Wichura's algorithm AS241 (PPND16), about 16 significant digits
+    if x.is_nan() || !(0.0..=1.0).contains(&x) {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let polynomial =
+        |c: &[f64], r: f64| -> f64 { c.iter().rev().fold(0.0, |acc, &ci| acc * r + ci) };
+
+    let q = x - 0.5;
+    if q.abs() <= 0.425 {
+        let r = 0.180625 - q * q;
+        return q * polynomial(&AS241_A, r) / polynomial(&AS241_B, r);
+    }
+
+    let r = (-f64::min(x, 1.0 - x).ln()).sqrt();
+    let value = if r <= 5.0 {
+        polynomial(&AS241_C, r - 1.6) / polynomial(&AS241_D, r - 1.6)
+    } else {
+        polynomial(&AS241_E, r - 5.0) / polynomial(&AS241_F, r - 5.0)
+    };
+
+    if q < 0.0 {
+        -value
     } else {
-        5.5556 * (1.0 - f64::powf((1.0 - x) / x, 0.1186))
+        value
     }
 }
 
+// Coefficients of the AS241 rational approximations, in ascending powers
+const AS241_A: [f64; 8] = [
+    3.3871328727963665,
+    133.14166789178438,
+    1971.5909503065513,
+    13731.69376550946,
+    45921.95393154987,
+    67265.7709270087,
+    33430.57558358813,
+    2509.0809287301227,
+];
+const AS241_B: [f64; 8] = [
+    1.0,
+    42.31333070160091,
+    687.1870074920579,
+    5394.196021424751,
+    21213.794301586597,
+    39307.89580009271,
+    28729.085735721943,
+    5226.495278852854,
+];
+const AS241_C: [f64; 8] = [
+    1.4234371107496835,
+    4.630337846156546,
+    5.769497221460691,
+    3.6478483247632045,
+    1.2704582524523684,
+    0.2417807251774506,
+    0.022723844989269184,
+    0.0007745450142783414,
+];
+const AS241_D: [f64; 8] = [
+    1.0,
+    2.053191626637759,
+    1.6763848301838038,
+    0.6897673349851,
+    0.14810397642748008,
+    0.015198666563616457,
+    0.0005475938084995345,
+    1.0507500716444169e-09,
+];
+const AS241_E: [f64; 8] = [
+    6.657904643501103,
+    5.463784911164114,
+    1.7848265399172913,
+    0.29656057182850487,
+    0.026532189526576124,
+    0.0012426609473880784,
+    2.7115555687434876e-05,
+    2.0103343992922881e-07,
+];
+const AS241_F: [f64; 8] = [
+    1.0,
+    0.599832206555888,
+    0.1369298809227358,
+    0.014875361290850615,
+    0.0007868691311456133,
+    1.8463183175100548e-05,
+    1.421511758316446e-07,
+    2.0442631033899397e-15,
+];
+
 // Gaussian quantile
 #[wasm_bindgen]
 pub fn norminv(x: f64, mu: f64, sigma: f64) -> f64 {
@@ -61,80 +235,348 @@ pub fn norminv(x: f64, mu: f64, sigma: f64) -> f64 {
 /**
  *  --------------------------------------------------------------
  *  GAMMA DISTRIBUTION
- *  Gamma functions (complete, lower incomplete, upper incomplete)
+ *  Gamma functions (complete, logarithmic, lower incomplete, upper incomplete, regularized)
+ *  Digamma and trigamma functions
  *  Gamma distribution (pdf, cdf, quantile)
  *  --------------------------------------------------------------
  */
 
-// Gamma function
+// Lanczos approximation (g = 7, n = 9), about 15 significant digits for x >= 0.5
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+// Lanczos series A(x), with x >= 0.5
+fn lanczos_sum(x: f64) -> f64 {
+    LANCZOS_COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS_COEFFICIENTS[0], |acc, (i, c)| {
+            acc + c / (x + i as f64)
+        })
+}
+
+// Gamma function, with the reflection formula for x < 0.5.
+// Like MATLAB, it returns Inf on the poles (0 and the negative integers).
 #[wasm_bindgen]
 pub fn gamma(x: f64) -> f64 {
-    // coefficients from Chebyshev polynomials
-    let q = vec![
-        75122.6331530,
-        80916.6278952,
-        36308.2951477,
-        8687.24529705,
-        1168.92649479,
-        83.8676043424,
-        2.50662827511,
-    ];
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        return f64::NAN;
+    }
+    if x <= 0.0 && x == x.floor() || x == f64::INFINITY {
+        return f64::INFINITY;
+    }
+
+    // Exact factorials
+    if x == x.floor() && x <= 171.0 {
+        return (2..x as u64).fold(1.0, |acc, k| acc * k as f64);
+    }
+
+    if x < 0.5 {
+        return PI / ((PI * x).sin() * gamma(1.0 - x));
+    }
+
+    // sqrt(2 pi) t^(x - 1/2) e^-t A(x), with the power split in two halves to delay the overflow
+    let t = x + LANCZOS_G - 0.5;
+    let half_power = t.powf(0.5 * (x - 0.5));
+    (2.0 * PI).sqrt() * half_power * (half_power * (-t).exp()) * lanczos_sum(x)
+}
+
+// Natural logarithm of the absolute value of the gamma function
+#[wasm_bindgen]
+pub fn lgamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        return f64::NAN;
+    }
+    if x <= 0.0 && x == x.floor() || x == f64::INFINITY {
+        return f64::INFINITY;
+    }
+
+    // ln(gamma(1)) = ln(gamma(2)) = 0 exactly
+    if x == 1.0 || x == 2.0 {
+        return 0.0;
+    }
+
+    if x < 0.5 {
+        return (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x);
+    }
+
+    let t = x + LANCZOS_G - 0.5;
+    0.5 * (2.0 * PI).ln() + (x - 0.5) * t.ln() - t + lanczos_sum(x).ln()
+}
+
+// Digamma function, the logarithmic derivative of gamma
+#[wasm_bindgen]
+pub fn digamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        return f64::NAN;
+    }
+    if x <= 0.0 && x == x.floor() {
+        return f64::NAN;
+    }
+
+    // Reflection: psi(1 - x) - psi(x) = pi cot(pi x)
+    if x < 0.0 {
+        return digamma(1.0 - x) - PI / (PI * x).tan();
+    }
+
+    // Recurrence psi(x + 1) = psi(x) + 1/x up to where the asymptotic series is accurate
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 10.0 {
+        result -= x.recip();
+        x += 1.0;
+    }
+
+    let x2 = (x * x).recip();
+    result + x.ln()
+        - 0.5 / x
+        - x2 * (1.0 / 12.0
+            - x2 * (1.0 / 120.0
+                - x2 * (1.0 / 252.0
+                    - x2 * (1.0 / 240.0 - x2 * (1.0 / 132.0 - x2 * 691.0 / 32760.0)))))
+}
+
+// Trigamma function, the derivative of digamma
+#[wasm_bindgen]
+pub fn trigamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        return f64::NAN;
+    }
+    if x <= 0.0 && x == x.floor() {
+        return f64::INFINITY;
+    }
+
+    // Reflection: psi1(1 - x) + psi1(x) = pi^2 / sin^2(pi x)
+    if x < 0.0 {
+        return -trigamma(1.0 - x) + (PI / (PI * x).sin()).powi(2);
+    }
+
+    // Recurrence psi1(x + 1) = psi1(x) - 1/x^2
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 10.0 {
+        result += (x * x).recip();
+        x += 1.0;
+    }
+
+    let x2 = (x * x).recip();
+    result
+        + x.recip()
+        + 0.5 * x2
+        + x2 / x
+            * (1.0 / 6.0
+                - x2 * (1.0 / 30.0
+                    - x2 * (1.0 / 42.0
+                        - x2 * (1.0 / 30.0
+                            - x2 * (5.0 / 66.0 - x2 * (691.0 / 2730.0 - x2 * 7.0 / 6.0))))))
+}
+
+// Smallest representable number used by the modified Lentz algorithm
+const LENTZ_TINY: f64 = 1e-300;
+
+// x^s e^-x / gamma(s), the prefactor shared by the incomplete gamma expansions
+fn incgamma_prefactor(s: f64, x: f64) -> f64 {
+    (s * x.ln() - x - lgamma(s)).exp()
+}
+
+// Series expansion of P(s, x), converging quickly for x < s + 1
+fn incgamma_series(s: f64, x: f64) -> f64 {
+    let mut term = s.recip();
+    let mut sum = term;
+    let mut denominator = s;
 
-    let mut num: f64 = 0.0;
-    let mut den: f64 = 1.0;
+    for _iteration in 0..100_000 {
+        denominator += 1.0;
+        term *= x / denominator;
+        sum += term;
+        if term.abs() < sum.abs() * f64::EPSILON {
+            break;
+        }
+    }
+
+    sum * incgamma_prefactor(s, x)
+}
+
+// Continued fraction for ln Q(s, x) (modified Lentz algorithm), converging quickly for x > s + 1.
+// The logarithm stays finite far in the tail, where Q itself underflows.
+fn ln_incgamma_fraction(s: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - s;
+    let mut c = LENTZ_TINY.recip();
+    let mut d = b.recip();
+    let mut h = d;
+
+    for i in 1..100_000 {
+        let an = -(i as f64) * (i as f64 - s);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < LENTZ_TINY {
+            d = LENTZ_TINY;
+        }
+        c = b + an / c;
+        if c.abs() < LENTZ_TINY {
+            c = LENTZ_TINY;
+        }
+        d = d.recip();
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+
+    h.ln() + s * x.ln() - x - lgamma(s)
+}
 
-    for n in 0..=6 {
-        num += q[n] * x.powi(n as i32);
-        den *= x + (n as f64);
+fn incgamma_fraction(s: f64, x: f64) -> f64 {
+    ln_incgamma_fraction(s, x).exp()
+}
+
+// Regularized lower incomplete gamma function P(s, x)
+// (MATLAB's gammainc(x, s))
+#[wasm_bindgen]
+pub fn regincgamma(s: f64, x: f64) -> f64 {
+    if s.is_nan() || x.is_nan() || s <= 0.0 || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        0.0
+    } else if x == f64::INFINITY {
+        1.0
+    } else if x < s + 1.0 {
+        incgamma_series(s, x)
+    } else {
+        1.0 - incgamma_fraction(s, x)
     }
+}
 
-    // Lanczos's approximation
-    num * den.recip() * (x + 5.5).powf(x + 0.5) * f64::exp(-(x + 5.5))
+// Regularized upper incomplete gamma function Q(s, x) = 1 - P(s, x)
+// (MATLAB's gammainc(x, s, 'upper'))
+#[wasm_bindgen]
+pub fn reguppincgamma(s: f64, x: f64) -> f64 {
+    if s.is_nan() || x.is_nan() || s <= 0.0 || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        1.0
+    } else if x == f64::INFINITY {
+        0.0
+    } else if x < s + 1.0 {
+        1.0 - incgamma_series(s, x)
+    } else {
+        incgamma_fraction(s, x)
+    }
 }
 
 // Lower incomplete gamma function
 #[wasm_bindgen]
 pub fn lowincgamma(s: f64, x: f64) -> f64 {
-    gamma(s) * gammapdf(x, s, 1.0)
+    gamma(s) * regincgamma(s, x)
 }
 
 // Upper incomplete gamma function
 #[wasm_bindgen]
 pub fn uppincgamma(s: f64, x: f64) -> f64 {
-    gamma(s) * (1.0 - gammapdf(x, s, 1.0))
+    gamma(s) * reguppincgamma(s, x)
 }
 
-// Regularized incomplete gamma function
+// Inverse of the regularized lower incomplete gamma function:
+// the x for which P(s, x) = p (Halley's method, Numerical Recipes' starting guesses)
 #[wasm_bindgen]
-pub fn regincgamma(s: f64, x: f64) -> f64 {
-    gamma(s) * (1.0 - gammapdf(x, s, 1.0))
+pub fn regincgammainv(p: f64, s: f64) -> f64 {
+    if p.is_nan() || s.is_nan() || s <= 0.0 || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    } else if p == 0.0 {
+        return 0.0;
+    } else if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let s1 = s - 1.0;
+    let log_gamma = lgamma(s);
+
+    let mut x = if s > 1.0 {
+        // Wilson-Hilferty approximation
+        let t = (-2.0 * f64::min(p, 1.0 - p).ln()).sqrt();
+        let mut z = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            z = -z;
+        }
+        f64::max(
+            1e-3,
+            s * (1.0 - (9.0 * s).recip() - z / (3.0 * s.sqrt())).powi(3),
+        )
+    } else {
+        let t = 1.0 - s * (0.253 + s * 0.12);
+        if p < t {
+            (p / t).powf(s.recip())
+        } else {
+            1.0 - (1.0 - (p - t) / (1.0 - t)).ln()
+        }
+    };
+
+    for _iteration in 0..100 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        let error = regincgamma(s, x) - p;
+        let density = (s1 * x.ln() - x - log_gamma).exp();
+        if density == 0.0 {
+            break;
+        }
+
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * f64::min(1.0, u * (s1 / x - 1.0)));
+        x -= step;
+        if x <= 0.0 {
+            // Halve the distance to zero instead of overshooting
+            x = 0.5 * (x + step);
+        }
+
+        if step.abs() <= 4.0 * f64::EPSILON * x {
+            break;
+        }
+    }
+
+    x
 }
 
-// Gamma distribution
+// Gamma distribution with shape a and scale b, like MATLAB's gampdf
 #[wasm_bindgen]
 pub fn gammapdf(x: f64, a: f64, b: f64) -> f64 {
     if x < 0.0 {
         return f64::NAN;
     }
-    x.powf(a - 1.0) * f64::exp(-b * x) * b.powf(a) / gamma(a)
+    ((a - 1.0) * x.ln() - x / b - a * b.ln() - lgamma(a)).exp()
 }
 
-// Cumulate gamma distribution
+// Cumulate gamma distribution with shape a and scale b
 #[wasm_bindgen]
 pub fn gammacdf(x: f64, a: f64, b: f64) -> f64 {
-    unimplemented!()
+    if x <= 0.0 {
+        return 0.0;
+    }
+    regincgamma(a, x / b)
 }
 
-// Ggamma distribution quantile
+// Gamma distribution quantile with shape a and scale b
 #[wasm_bindgen]
 pub fn gammainv(x: f64, a: f64, b: f64) -> f64 {
-    unimplemented!()
+    b * regincgammainv(x, a)
 }
 
 /**
  *  -------------------------------------------------
  *  BETA DISTRIBUTION
- *  Beta function (complete, incomplete, regularized)
+ *  Beta function (complete, logarithmic, incomplete, regularized)
  *  Beta distribution (pdf, cdf, quantile)
  *  -------------------------------------------------
  */
@@ -142,21 +584,159 @@ pub fn gammainv(x: f64, a: f64, b: f64) -> f64 {
 // Beta function
 #[wasm_bindgen]
 pub fn beta(x: f64, y: f64) -> f64 {
-    gamma(x) * gamma(y) / gamma(x + y)
+    if x > 0.0 && y > 0.0 {
+        betaln(x, y).exp()
+    } else {
+        gamma(x) * gamma(y) / gamma(x + y)
+    }
+}
+
+// Natural logarithm of the beta function, for positive arguments
+#[wasm_bindgen]
+pub fn betaln(x: f64, y: f64) -> f64 {
+    lgamma(x) + lgamma(y) - lgamma(x + y)
+}
+
+// Continued fraction for the incomplete beta function (modified Lentz algorithm),
+// converging quickly for x < (a + 1) / (a + b + 2)
+fn incbet_fraction(x: f64, a: f64, b: f64) -> f64 {
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < LENTZ_TINY {
+        d = LENTZ_TINY;
+    }
+    d = d.recip();
+    let mut h = d;
+
+    for m in 1..100_000 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // Even step
+        let an = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + an * d;
+        if d.abs() < LENTZ_TINY {
+            d = LENTZ_TINY;
+        }
+        c = 1.0 + an / c;
+        if c.abs() < LENTZ_TINY {
+            c = LENTZ_TINY;
+        }
+        d = d.recip();
+        h *= d * c;
+
+        // Odd step
+        let an = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + an * d;
+        if d.abs() < LENTZ_TINY {
+            d = LENTZ_TINY;
+        }
+        c = 1.0 + an / c;
+        if c.abs() < LENTZ_TINY {
+            c = LENTZ_TINY;
+        }
+        d = d.recip();
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+
+    h
 }
 
 // Incomplete Beta Function
 #[wasm_bindgen]
 pub fn incbet(x: f64, a: f64, b: f64) -> f64 {
-    // I had to give up and use numerical integration
-    let beta_deriv = |t: f64| -> f64 { t.powf(a - 1.0) * (1.0 - t).powf(b - 1.0) };
-    integrate(beta_deriv, 0.0, x, 256)
+    regincbet(x, a, b) * beta(a, b)
 }
 
 // Regularized incomplete beta function
+// (MATLAB's betainc(x, a, b))
 #[wasm_bindgen]
 pub fn regincbet(x: f64, a: f64, b: f64) -> f64 {
-    incbet(x, a, b) / beta(a, b)
+    if x.is_nan() || a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 || !(0.0..=1.0).contains(&x) {
+        return f64::NAN;
+    } else if x == 0.0 || x == 1.0 {
+        return x;
+    }
+
+    // x^a (1 - x)^b / B(a, b)
+    let prefactor = (a * x.ln() + b * (1.0 - x).ln() - betaln(a, b)).exp();
+
+    // The fraction converges on one side of the mean, the symmetry relation
+    // I_x(a, b) = 1 - I_(1-x)(b, a) covers the other one
+    if x < (a + 1.0) / (a + b + 2.0) {
+        prefactor * incbet_fraction(x, a, b) / a
+    } else {
+        1.0 - prefactor * incbet_fraction(1.0 - x, b, a) / b
+    }
+}
+
+// Inverse of the regularized incomplete beta function:
+// the x for which I_x(a, b) = p (Halley's method, Numerical Recipes' starting guesses)
+#[wasm_bindgen]
+pub fn regincbetinv(p: f64, a: f64, b: f64) -> f64 {
+    if p.is_nan() || a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    } else if p == 0.0 || p == 1.0 {
+        return p;
+    }
+
+    let (a1, b1) = (a - 1.0, b - 1.0);
+
+    let mut x = if a >= 1.0 && b >= 1.0 {
+        let t = (-2.0 * f64::min(p, 1.0 - p).ln()).sqrt();
+        let mut z = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            z = -z;
+        }
+        let al = (z * z - 3.0) / 6.0;
+        let h = 2.0 / ((2.0 * a - 1.0).recip() + (2.0 * b - 1.0).recip());
+        let w = z * (al + h).sqrt() / h
+            - ((2.0 * b - 1.0).recip() - (2.0 * a - 1.0).recip())
+                * (al + 5.0 / 6.0 - 2.0 / (3.0 * h));
+        a / (a + b * (2.0 * w).exp())
+    } else {
+        let t = (a * (a / (a + b)).ln()).exp() / a;
+        let u = (b * (b / (a + b)).ln()).exp() / b;
+        let w = t + u;
+        if p < t / w {
+            (a * w * p).powf(a.recip())
+        } else {
+            1.0 - (b * w * (1.0 - p)).powf(b.recip())
+        }
+    };
+
+    let log_beta = betaln(a, b);
+
+    for _iteration in 0..100 {
+        if x == 0.0 || x == 1.0 {
+            return x;
+        }
+
+        let error = regincbet(x, a, b) - p;
+        let density = (a1 * x.ln() + b1 * (1.0 - x).ln() - log_beta).exp();
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * f64::min(1.0, u * (a1 / x - b1 / (1.0 - x))));
+        x -= step;
+
+        // Bisect towards the boundary instead of leaving [0, 1]
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if x >= 1.0 {
+            x = 0.5 * (x + step + 1.0);
+        }
+
+        if step.abs() <= 4.0 * f64::EPSILON * x {
+            break;
+        }
+    }
+
+    x
 }
 
 // Beta distribution
@@ -168,13 +748,13 @@ pub fn betapdf(x: f64, a: f64, b: f64) -> f64 {
 // Cumulative beta distribution
 #[wasm_bindgen]
 pub fn betacdf(x: f64, a: f64, b: f64) -> f64 {
-    unimplemented!()
+    regincbet(x.clamp(0.0, 1.0), a, b)
 }
 
 // Beta distribution quantile
 #[wasm_bindgen]
 pub fn betainv(x: f64, a: f64, b: f64) -> f64 {
-    unimplemented!()
+    regincbetinv(x, a, b)
 }
 
 /**
@@ -213,23 +793,49 @@ pub fn tcdf(x: f64, v: f64) -> f64 {
 // t distribution quantile
 #[wasm_bindgen]
 pub fn tinv(x: f64, v: f64) -> f64 {
-    let to_maximize = |tval: f64| -> f64 { x - tcdf(tval, v) };
+    if !(0.0..=1.0).contains(&x) {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x == 1.0 {
+        return f64::INFINITY;
+    }
 
-    if x < i8::MIN as f64 {
-        f64::NEG_INFINITY
-    } else if x > i8::MAX as f64 {
-        f64::INFINITY
-    } else if x > 0.000000001 && x < 0.999999999 {
-        fzero(to_maximize, -10.0, 10.0)
-    } else {
-        fzero(to_maximize, i8::MIN as f64, i8::MAX as f64)
+    cdf_root(|t| tcdf(t, v), x, false)
+}
+
+// Root of cdf(x) = p, widening a bracket from [-1, 1], or from [0, 1] for a support bounded below by 0
+fn cdf_root<F: Fn(f64) -> f64>(cdf: F, p: f64, positive: bool) -> f64 {
+    let to_zero = |x: f64| -> f64 { cdf(x) - p };
+
+    // Widen the interval until it brackets the quantile. A bound that overflows means the
+    // cdf, rounded, doesn't reach p at any finite x: the quantile is infinite if it reaches
+    // p at infinity, NaN otherwise
+    let mut left = if positive { 0.0 } else { -1.0 };
+    let mut right = 1.0;
+    while !positive && to_zero(left) > 0.0 {
+        left *= 2.0;
+        if left.is_infinite() {
+            return if cdf(left) <= p { left } else { f64::NAN };
+        }
+    }
+    while to_zero(right) < 0.0 {
+        right *= 2.0;
+        if right.is_infinite() {
+            return if cdf(right) >= p { right } else { f64::NAN };
+        }
+    }
+
+    match fzero(to_zero, left, right, 1e-14) {
+        Ok(root) => root.x,
+        Err(_) => f64::NAN,
     }
 }
 
 /**
 *   ----------------------------------------
 *   CHI SQUARED DISTRIBUTION
-*   X^2 distribition
+*   X^2 distribition (central and noncentral)
 *   Density, Cumulate and Quantile functions
 *   ----------------------------------------
 */
@@ -237,7 +843,7 @@ pub fn tinv(x: f64, v: f64) -> f64 {
 #[wasm_bindgen]
 pub fn chi2pdf(x: f64, k: f64) -> f64 {
     if x > 0.0 {
-        x.powf(0.5 * k - 1.0) * f64::exp(-0.5 * x) * (-0.5 * k).exp2() / gamma(0.5 * k)
+        gammapdf(x, 0.5 * k, 2.0)
     } else {
         0.0
     }
@@ -245,319 +851,5116 @@ pub fn chi2pdf(x: f64, k: f64) -> f64 {
 
 #[wasm_bindgen]
 pub fn chi2cdf(x: f64, k: f64) -> f64 {
-    gamma(x * 0.5).recip() * lowincgamma(k * 0.5, x * 0.5)
+    gammacdf(x, 0.5 * k, 2.0)
 }
 
 #[wasm_bindgen]
 pub fn chi2inv(x: f64, k: f64) -> f64 {
-    x + (4.0 * (k - 1.0)).recip() * x * x
+    gammainv(x, 0.5 * k, 2.0)
 }
 
-/**
- * |----------------------------|
- * |----------------------------|
- * |         UNIT TESTS         |
- * |----------------------------|
- * |----------------------------|
- */
+// Largest Poisson mean of a mixture, about 10 sqrt(h) terms being needed on each side of the mode
+const MIXTURE_MAX_MEAN: f64 = 1e6;
+const MIXTURE_MAX_TERMS: u32 = 100_000;
 
-#[cfg(test)]
-mod tests {
+// Poisson mixture sum_j w_j term(j), with weights w_j = e^-h h^j / j!,
+// summed outwards from the largest weight until the weights become negligible.
+// NaN for a mean that is negative, NaN, or above MIXTURE_MAX_MEAN.
+fn poisson_mixture<F: Fn(f64) -> f64>(h: f64, term: F) -> f64 {
+    if !(0.0..=MIXTURE_MAX_MEAN).contains(&h) {
+        return f64::NAN;
+    } else if h == 0.0 {
+        return term(0.0);
+    }
 
-    use super::*;
+    let weight = |j: f64| -> f64 { (-h + j * h.ln() - lgamma(j + 1.0)).exp() };
+    let mode = h.floor();
+    let mut sum = 0.0;
 
-    /**
-     *  ------------------------------
-     *  NORMAL DISTRIBUTION UNIT TESTS
-     *  ------------------------------
-     */
+    for k in 0..MIXTURE_MAX_TERMS {
+        let j = mode + f64::from(k);
+        let w = weight(j);
+        sum += w * term(j);
+        if w < f64::EPSILON * 1e-3 {
+            break;
+        }
+    }
 
-    #[test]
-    fn normpdf_test() {
-        // Does the normal probability function compute the correct result?
-        assert_eq!(normpdf(1.0, 1.0, 1.0), 0.3989422804014327);
+    for k in 1..=MIXTURE_MAX_TERMS.min(mode as u32) {
+        let j = mode - f64::from(k);
+        let w = weight(j);
+        sum += w * term(j);
+        if w < f64::EPSILON * 1e-3 {
+            break;
+        }
     }
 
-    #[test]
-    fn s_normpdf_test() {
-        // Does the normal probability function compute the correct result?
+    sum
+}
 
-        //  Approximate the result
-        // (two different square root algorithms, so slightly different results are expected)
-        let s_norm: String = s_normpdf(1.0).to_string().chars().take(5).collect();
-        let norm: String = normpdf(1.0, 0.0, 1.0).to_string().chars().take(5).collect();
+// Sankaran's normal approximation of the noncentral chi-squared distribution, for
+// noncentralities beyond the Poisson mixture: z(x) with cdf = Phi(z), and dz/dx
+fn ncx2_sankaran(x: f64, k: f64, lambda: f64) -> (f64, f64) {
+    let mean = k + lambda;
+    let h = 1.0 - 2.0 / 3.0 * mean * (k + 3.0 * lambda) / ((k + 2.0 * lambda) * (k + 2.0 * lambda));
+    let p = (k + 2.0 * lambda) / (mean * mean);
+    let m = (h - 1.0) * (1.0 - 3.0 * h);
+    let scale = h * (2.0 * p).sqrt() * (1.0 + 0.5 * m * p);
 
-        assert_eq!(s_norm, norm);
-    }
+    let z = ((x / mean).powf(h) - (1.0 + h * p * (h - 1.0 - 0.5 * (2.0 - h) * m * p))) / scale;
+    let dz = h * (x / mean).powf(h - 1.0) / mean / scale;
+    (z, dz)
+}
 
-    #[test]
-    fn normcdf_test() {
-        // Does the cumulative probability function compute the correct result?
-        assert_eq!(normcdf(0.5, 1.0, 2.0), 0.4012880670269864);
+// Noncentral chi-squared distribution with noncentrality parameter lambda
+#[wasm_bindgen]
+pub fn ncx2pdf(x: f64, k: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if lambda == f64::INFINITY {
+        return 0.0;
+    } else if 0.5 * lambda > MIXTURE_MAX_MEAN {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let (z, dz) = ncx2_sankaran(x, k, lambda);
+        return s_normpdf(z) * dz;
     }
+    poisson_mixture(0.5 * lambda, |j| chi2pdf(x, k + 2.0 * j))
+}
 
-    #[test]
-    fn s_normcdf_test() {
-        // Does the cumulative probability function compute the correct result?
-        assert_eq!(s_normcdf(0.0), 0.5000000005248086);
+// Cumulate noncentral chi-squared distribution
+#[wasm_bindgen]
+pub fn ncx2cdf(x: f64, k: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if lambda == f64::INFINITY {
+        return if x == f64::INFINITY { 1.0 } else { 0.0 };
+    } else if 0.5 * lambda > MIXTURE_MAX_MEAN {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        return s_normcdf(ncx2_sankaran(x, k, lambda).0);
     }
+    poisson_mixture(0.5 * lambda, |j| chi2cdf(x, k + 2.0 * j)).min(1.0)
+}
 
-    #[test]
-    fn s_norminv_test() {
-        // Does the inverse probability function compute the correct result?
-        assert_eq!(s_norminv(0.5), 0.0);
-        assert_eq!(s_norminv(0.3), -0.531145444833719);
-    }
+//  ------------------------------------------------------------
+//  OTHER CONTINUOUS DISTRIBUTIONS
+//  F, exponential, log-normal, Weibull, Cauchy, Laplace,
+//  logistic, Pareto and uniform distributions
+//  Density, Cumulate and Quantile functions
+//  ------------------------------------------------------------
 
-    #[test]
-    fn norminv_test() {
-        // Does the inverse probability function compute the correct result?
-        assert_eq!(norminv(0.3, 4.0, 2.0), 2.937709110332562)
+// Whether x is a probability, the domain of the quantile functions
+fn is_probability(x: f64) -> bool {
+    (0.0..=1.0).contains(&x)
+}
+
+// F distribution with v1 and v2 degrees of freedom
+#[wasm_bindgen]
+pub fn fpdf(x: f64, v1: f64, v2: f64) -> f64 {
+    if v1.is_nan() || v2.is_nan() || v1 <= 0.0 || v2 <= 0.0 {
+        return f64::NAN;
+    } else if x < 0.0 {
+        return 0.0;
+    } else if x == 0.0 {
+        return if v1 < 2.0 {
+            f64::INFINITY
+        } else if v1 == 2.0 {
+            1.0
+        } else {
+            0.0
+        };
     }
 
-    /**
-     *  -----------------------------
-     *  GAMMA DISTRIBUTION UNIT TESTS
-     *  -----------------------------
-     */
+    (0.5 * v1 * v1.ln() + 0.5 * v2 * v2.ln() + (0.5 * v1 - 1.0) * x.ln()
+        - 0.5 * (v1 + v2) * (v2 + v1 * x).ln()
+        - betaln(0.5 * v1, 0.5 * v2))
+    .exp()
+}
 
-    #[test]
-    fn gamma_test() {
-        // Extract the result
-        let gamma = gamma(2.3);
+// Cumulate F distribution
+#[wasm_bindgen]
+pub fn fcdf(x: f64, v1: f64, v2: f64) -> f64 {
+    if v1.is_nan() || v2.is_nan() || v1 <= 0.0 || v2 <= 0.0 {
+        return f64::NAN;
+    } else if x <= 0.0 {
+        return 0.0;
+    } else if x == f64::INFINITY {
+        return 1.0;
+    }
 
-        // Round to 5 digits
-        let gamma = (gamma * 10000.0).round() / 10000.0;
+    // Take the smaller of the two beta arguments for accuracy
+    let z = v1 * x;
+    if z < v2 {
+        regincbet(z / (z + v2), 0.5 * v1, 0.5 * v2)
+    } else {
+        1.0 - regincbet(v2 / (z + v2), 0.5 * v2, 0.5 * v1)
+    }
+}
 
-        // Compare with MATLAB result
-        assert_eq!(gamma, 1.1667);
+// F distribution quantile
+#[wasm_bindgen]
+pub fn finv(x: f64, v1: f64, v2: f64) -> f64 {
+    if v1.is_nan() || v2.is_nan() || v1 <= 0.0 || v2 <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    } else if x == 1.0 {
+        return f64::INFINITY;
     }
 
-    #[test]
-    fn lowincgamma_test() {
-        // Extract the result
-        let lowincgamma = lowincgamma(1.44, 3.5);
+    let y = regincbetinv(x, 0.5 * v1, 0.5 * v2);
+    v2 * y / (v1 * (1.0 - y))
+}
 
-        // Round to 5 digits
+// Exponential distribution with mean mu, like MATLAB's exppdf
+#[wasm_bindgen]
+pub fn exppdf(x: f64, mu: f64) -> f64 {
+    if mu.is_nan() || mu <= 0.0 {
+        f64::NAN
+    } else if x < 0.0 {
+        0.0
+    } else {
+        (-x / mu).exp() / mu
+    }
+}
+
+// Cumulate exponential distribution
+#[wasm_bindgen]
+pub fn expcdf(x: f64, mu: f64) -> f64 {
+    if mu.is_nan() || mu <= 0.0 {
+        f64::NAN
+    } else if x <= 0.0 {
+        0.0
+    } else {
+        -(-x / mu).exp_m1()
+    }
+}
+
+// Exponential distribution quantile
+#[wasm_bindgen]
+pub fn expinv(x: f64, mu: f64) -> f64 {
+    if mu.is_nan() || mu <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    }
+    -mu * (-x).ln_1p()
+}
+
+// Log-normal distribution: the logarithm of x is normal with mean mu and deviation sigma
+#[wasm_bindgen]
+pub fn lognpdf(x: f64, mu: f64, sigma: f64) -> f64 {
+    if sigma.is_nan() || sigma <= 0.0 {
+        f64::NAN
+    } else if x <= 0.0 {
+        0.0
+    } else {
+        normpdf(x.ln(), mu, sigma) / x
+    }
+}
+
+// Cumulate log-normal distribution
+#[wasm_bindgen]
+pub fn logncdf(x: f64, mu: f64, sigma: f64) -> f64 {
+    if sigma.is_nan() || sigma <= 0.0 {
+        f64::NAN
+    } else if x <= 0.0 {
+        0.0
+    } else {
+        normcdf(x.ln(), mu, sigma)
+    }
+}
+
+// Log-normal distribution quantile
+#[wasm_bindgen]
+pub fn logninv(x: f64, mu: f64, sigma: f64) -> f64 {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return f64::NAN;
+    }
+    norminv(x, mu, sigma).exp()
+}
+
+// Weibull distribution with scale a and shape b, like MATLAB's wblpdf
+#[wasm_bindgen]
+pub fn wblpdf(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 {
+        return f64::NAN;
+    } else if x < 0.0 {
+        return 0.0;
+    }
+
+    let z = x / a;
+    b / a * z.powf(b - 1.0) * (-z.powf(b)).exp()
+}
+
+// Cumulate Weibull distribution
+#[wasm_bindgen]
+pub fn wblcdf(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 {
+        f64::NAN
+    } else if x <= 0.0 {
+        0.0
+    } else {
+        -(-(x / a).powf(b)).exp_m1()
+    }
+}
+
+// Weibull distribution quantile
+#[wasm_bindgen]
+pub fn wblinv(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    }
+    a * (-(-x).ln_1p()).powf(b.recip())
+}
+
+// Cauchy distribution with location x0 and scale g
+#[wasm_bindgen]
+pub fn cauchypdf(x: f64, x0: f64, g: f64) -> f64 {
+    if g.is_nan() || g <= 0.0 {
+        return f64::NAN;
+    }
+
+    let z = (x - x0) / g;
+    (PI * g * (1.0 + z * z)).recip()
+}
+
+// Cumulate Cauchy distribution
+#[wasm_bindgen]
+pub fn cauchycdf(x: f64, x0: f64, g: f64) -> f64 {
+    if g.is_nan() || g <= 0.0 {
+        return f64::NAN;
+    }
+
+    // In the left tail 1/2 + atan(z)/pi would cancel, atan(-1/z)/pi does not
+    let z = (x - x0) / g;
+    if z < 0.0 {
+        (-z.recip()).atan() / PI
+    } else {
+        0.5 + z.atan() / PI
+    }
+}
+
+// Cauchy distribution quantile
+#[wasm_bindgen]
+pub fn cauchyinv(x: f64, x0: f64, g: f64) -> f64 {
+    if g.is_nan() || g <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x == 1.0 {
+        return f64::INFINITY;
+    }
+    x0 + g * (PI * (x - 0.5)).tan()
+}
+
+// Laplace distribution with location mu and scale b
+#[wasm_bindgen]
+pub fn laplacepdf(x: f64, mu: f64, b: f64) -> f64 {
+    if b.is_nan() || b <= 0.0 {
+        return f64::NAN;
+    }
+    (-(x - mu).abs() / b).exp() / (2.0 * b)
+}
+
+// Cumulate Laplace distribution
+#[wasm_bindgen]
+pub fn laplacecdf(x: f64, mu: f64, b: f64) -> f64 {
+    if b.is_nan() || b <= 0.0 {
+        return f64::NAN;
+    }
+
+    let z = (x - mu) / b;
+    if z < 0.0 {
+        0.5 * z.exp()
+    } else {
+        1.0 - 0.5 * (-z).exp()
+    }
+}
+
+// Laplace distribution quantile
+#[wasm_bindgen]
+pub fn laplaceinv(x: f64, mu: f64, b: f64) -> f64 {
+    if b.is_nan() || b <= 0.0 || !is_probability(x) {
+        f64::NAN
+    } else if x < 0.5 {
+        mu + b * (2.0 * x).ln()
+    } else {
+        mu - b * (2.0 * (1.0 - x)).ln()
+    }
+}
+
+// Logistic distribution with location mu and scale s
+#[wasm_bindgen]
+pub fn logisticpdf(x: f64, mu: f64, s: f64) -> f64 {
+    if s.is_nan() || s <= 0.0 {
+        return f64::NAN;
+    }
+
+    // Symmetric, so evaluate on the side where the exponential cannot overflow
+    let e = (-(x - mu).abs() / s).exp();
+    e / (s * (1.0 + e) * (1.0 + e))
+}
+
+// Cumulate logistic distribution
+#[wasm_bindgen]
+pub fn logisticcdf(x: f64, mu: f64, s: f64) -> f64 {
+    if s.is_nan() || s <= 0.0 {
+        return f64::NAN;
+    }
+    (1.0 + (-(x - mu) / s).exp()).recip()
+}
+
+// Logistic distribution quantile
+#[wasm_bindgen]
+pub fn logisticinv(x: f64, mu: f64, s: f64) -> f64 {
+    if s.is_nan() || s <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    }
+    mu + s * (x / (1.0 - x)).ln()
+}
+
+// Pareto distribution (type I) with scale xm, the lower bound of the support, and shape alpha
+#[wasm_bindgen]
+pub fn paretopdf(x: f64, xm: f64, alpha: f64) -> f64 {
+    if xm.is_nan() || alpha.is_nan() || xm <= 0.0 || alpha <= 0.0 {
+        f64::NAN
+    } else if x < xm {
+        0.0
+    } else {
+        alpha / x * (xm / x).powf(alpha)
+    }
+}
+
+// Cumulate Pareto distribution
+#[wasm_bindgen]
+pub fn paretocdf(x: f64, xm: f64, alpha: f64) -> f64 {
+    if xm.is_nan() || alpha.is_nan() || xm <= 0.0 || alpha <= 0.0 {
+        f64::NAN
+    } else if x <= xm {
+        0.0
+    } else {
+        -(alpha * (xm / x).ln()).exp_m1()
+    }
+}
+
+// Pareto distribution quantile
+#[wasm_bindgen]
+pub fn paretoinv(x: f64, xm: f64, alpha: f64) -> f64 {
+    if xm.is_nan() || alpha.is_nan() || xm <= 0.0 || alpha <= 0.0 || !is_probability(x) {
+        return f64::NAN;
+    }
+    xm * (-(-x).ln_1p() / alpha).exp()
+}
+
+// Continuous uniform distribution on [a, b]
+#[wasm_bindgen]
+pub fn unifpdf(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a >= b {
+        f64::NAN
+    } else if (a..=b).contains(&x) {
+        (b - a).recip()
+    } else {
+        0.0
+    }
+}
+
+// Cumulate uniform distribution
+#[wasm_bindgen]
+pub fn unifcdf(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a >= b {
+        return f64::NAN;
+    }
+    ((x - a) / (b - a)).clamp(0.0, 1.0)
+}
+
+// Uniform distribution quantile
+#[wasm_bindgen]
+pub fn unifinv(x: f64, a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a >= b || !is_probability(x) {
+        return f64::NAN;
+    }
+    a + x * (b - a)
+}
+
+//  ------------------------------------------------------------
+//  NONCENTRAL T AND F DISTRIBUTIONS
+//  Density, Cumulate and Quantile functions, and the power and
+//  sample size of t-tests and one-way ANOVA built on them
+//  ------------------------------------------------------------
+
+// Noncentral t cumulate distribution for x >= 0 and its complement, Lenth's algorithm AS 243.
+// Above the median, the complement is summed from the complementary incomplete beta
+// functions, so that the cumulate distribution doesn't level off below 1 in the right tail
+fn nct_right(x: f64, v: f64, delta: f64) -> (f64, f64) {
+    // y = x^2 / (x^2 + v) and 1 - y, without overflowing x^2
+    let y = 1.0 / (1.0 + v / (x * x));
+    let y_complement = v / (x * x + v);
+    let h = 0.5 * delta * delta;
+
+    // Beyond the Poisson mixtures, P(Z + delta <= x S) averaged over the standard normal Z,
+    // v S^2 being chi-squared with v degrees of freedom
+    if h > MIXTURE_MAX_MEAN {
+        let chi = |z: f64| -> f64 { 0.5 * v * (z + delta) * (z + delta) / (x * x) };
+        let lower = integrate(
+            |z| {
+                if z + delta <= 0.0 {
+                    s_normpdf(z)
+                } else {
+                    s_normpdf(z) * reguppincgamma(0.5 * v, chi(z))
+                }
+            },
+            -12.0,
+            12.0,
+            2000,
+        );
+        let upper = integrate(
+            |z| {
+                if z + delta <= 0.0 {
+                    0.0
+                } else {
+                    s_normpdf(z) * regincgamma(0.5 * v, chi(z))
+                }
+            },
+            -12.0,
+            12.0,
+            2000,
+        );
+        return (lower, upper);
+    }
+
+    // Mixtures divided by the sum of their weights, which rounds away from 1
+    let weights = poisson_mixture(h, |_| 1.0);
+    let ratio = |j: f64| -> f64 { (lgamma(j + 1.0) - lgamma(j + 1.5)).exp() };
+
+    let even = poisson_mixture(h, |j| regincbet(y, j + 0.5, 0.5 * v)) / weights;
+    let odd = poisson_mixture(h, |j| ratio(j) * regincbet(y, j + 1.0, 0.5 * v)) / weights;
+    let lower = s_normcdf(-delta) + 0.5 * (even + delta / SQRT_2 * odd);
+    if lower <= 0.5 {
+        return (lower, 1.0 - lower);
+    }
+
+    let even = poisson_mixture(h, |j| regincbet(y_complement, 0.5 * v, j + 0.5)) / weights;
+    let odd =
+        poisson_mixture(h, |j| ratio(j) * regincbet(y_complement, 0.5 * v, j + 1.0)) / weights;
+    let upper = 0.5 * (even + delta / SQRT_2 * odd);
+    (1.0 - upper, upper)
+}
+
+// Noncentral t distribution with v degrees of freedom and noncentrality delta
+#[wasm_bindgen]
+pub fn nctpdf(x: f64, v: f64, delta: f64) -> f64 {
+    if v.is_nan() || v <= 0.0 || delta.is_nan() {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return (lgamma(0.5 * (v + 1.0))
+            - lgamma(0.5 * v)
+            - 0.5 * (PI * v).ln()
+            - 0.5 * delta * delta)
+            .exp();
+    }
+
+    let shifted = nctcdf(x * (1.0 + 2.0 / v).sqrt(), v + 2.0, delta);
+    (v / x * (shifted - nctcdf(x, v, delta))).max(0.0)
+}
+
+// Cumulate noncentral t distribution
+#[wasm_bindgen]
+pub fn nctcdf(x: f64, v: f64, delta: f64) -> f64 {
+    if v.is_nan() || v <= 0.0 || delta.is_nan() || x.is_nan() {
+        return f64::NAN;
+    } else if x == f64::INFINITY {
+        return 1.0;
+    } else if x == f64::NEG_INFINITY || delta == f64::INFINITY {
+        return 0.0;
+    } else if delta == f64::NEG_INFINITY {
+        return 1.0;
+    }
+
+    let cdf = if x >= 0.0 {
+        nct_right(x, v, delta).0
+    } else {
+        nct_right(-x, v, -delta).1
+    };
+    cdf.clamp(0.0, 1.0)
+}
+
+// Noncentral t distribution quantile
+#[wasm_bindgen]
+pub fn nctinv(x: f64, v: f64, delta: f64) -> f64 {
+    if v.is_nan() || v <= 0.0 || delta.is_nan() || !is_probability(x) {
+        return f64::NAN;
+    } else if x == 0.0 {
+        return f64::NEG_INFINITY;
+    } else if x == 1.0 || delta == f64::INFINITY {
+        return f64::INFINITY;
+    } else if delta == f64::NEG_INFINITY {
+        return f64::NEG_INFINITY;
+    }
+    cdf_root(|t| nctcdf(t, v, delta), x, false)
+}
+
+// Patnaik's approximation of the noncentral F distribution by a scaled central one,
+// for noncentralities beyond the Poisson mixture: the scale and the numerator degrees of freedom
+fn ncf_patnaik(v1: f64, lambda: f64) -> (f64, f64) {
+    (
+        v1 / (v1 + lambda),
+        (v1 + lambda) * (v1 + lambda) / (v1 + 2.0 * lambda),
+    )
+}
+
+// Noncentral F distribution, a Poisson mixture of scaled central F distributions
+#[wasm_bindgen]
+pub fn ncfpdf(x: f64, v1: f64, v2: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if lambda == f64::INFINITY {
+        return 0.0;
+    } else if 0.5 * lambda > MIXTURE_MAX_MEAN {
+        let (scale, v) = ncf_patnaik(v1, lambda);
+        return scale * fpdf(x * scale, v, v2);
+    }
+    poisson_mixture(0.5 * lambda, |j| {
+        let scale = v1 / (v1 + 2.0 * j);
+        scale * fpdf(x * scale, v1 + 2.0 * j, v2)
+    })
+}
+
+// Cumulate noncentral F distribution
+#[wasm_bindgen]
+pub fn ncfcdf(x: f64, v1: f64, v2: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if lambda == f64::INFINITY {
+        return if x == f64::INFINITY { 1.0 } else { 0.0 };
+    } else if 0.5 * lambda > MIXTURE_MAX_MEAN {
+        let (scale, v) = ncf_patnaik(v1, lambda);
+        return fcdf(x * scale, v, v2);
+    }
+
+    // Divided by the sum of the weights, which rounds away from 1
+    let weights = poisson_mixture(0.5 * lambda, |_| 1.0);
+    let mixture = poisson_mixture(0.5 * lambda, |j| {
+        fcdf(x * v1 / (v1 + 2.0 * j), v1 + 2.0 * j, v2)
+    });
+    (mixture / weights).min(1.0)
+}
+
+// Noncentral F distribution quantile
+#[wasm_bindgen]
+pub fn ncfinv(x: f64, v1: f64, v2: f64, lambda: f64) -> f64 {
+    if v1.is_nan()
+        || v2.is_nan()
+        || v1 <= 0.0
+        || v2 <= 0.0
+        || lambda.is_nan()
+        || lambda < 0.0
+        || !is_probability(x)
+    {
+        return f64::NAN;
+    } else if x == 1.0 || lambda == f64::INFINITY {
+        return f64::INFINITY;
+    }
+    cdf_root(|f| ncfcdf(f, v1, v2, lambda), x, true)
+}
+
+// Probability that a t-test with v degrees of freedom rejects, when the statistic has noncentrality delta
+fn t_power(v: f64, delta: f64, alpha: f64, two_sided: bool) -> f64 {
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return f64::NAN;
+    }
+
+    if two_sided {
+        let critical = tinv(1.0 - 0.5 * alpha, v);
+        1.0 - nctcdf(critical, v, delta) + nctcdf(-critical, v, delta)
+    } else {
+        1.0 - nctcdf(tinv(1.0 - alpha, v), v, delta)
+    }
+}
+
+// Smallest integer n >= minimum reaching the power, for a power increasing with n
+fn sample_size<P: Fn(f64) -> f64>(
+    power_of: P,
+    power: f64,
+    alpha: f64,
+    minimum: f64,
+    guess: f64,
+) -> f64 {
+    if !(alpha > 0.0 && alpha < power && power < 1.0) || power_of(minimum).is_nan() {
+        return f64::NAN;
+    }
+    discrete_quantile(power_of, power, minimum, f64::INFINITY, guess)
+}
+
+// Power of a one-sample (or paired) t-test with n observations for an effect size d
+// (Cohen's d, the mean difference in standard deviations) at significance level alpha
+#[wasm_bindgen]
+pub fn ttest_power(d: f64, n: f64, alpha: f64, two_sided: bool) -> f64 {
+    if n.is_nan() || n < 2.0 {
+        return f64::NAN;
+    }
+    t_power(n - 1.0, d * n.sqrt(), alpha, two_sided)
+}
+
+// Number of observations for a one-sample (or paired) t-test to reach the power
+#[wasm_bindgen]
+pub fn ttest_samplesize(d: f64, power: f64, alpha: f64, two_sided: bool) -> f64 {
+    if !d.is_finite() {
+        return f64::NAN;
+    } else if d == 0.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = if two_sided { 0.5 * alpha } else { alpha };
+    let guess = ((s_norminv(1.0 - tail) + s_norminv(power)) / d).powi(2);
+    sample_size(
+        |n| ttest_power(d.abs(), n, alpha, two_sided),
+        power,
+        alpha,
+        2.0,
+        guess,
+    )
+}
+
+// Power of a two-sample t-test with n observations in each group
+#[wasm_bindgen]
+pub fn ttest2_power(d: f64, n: f64, alpha: f64, two_sided: bool) -> f64 {
+    if n.is_nan() || n < 2.0 {
+        return f64::NAN;
+    }
+    t_power(2.0 * n - 2.0, d * (0.5 * n).sqrt(), alpha, two_sided)
+}
+
+// Number of observations in each group for a two-sample t-test to reach the power
+#[wasm_bindgen]
+pub fn ttest2_samplesize(d: f64, power: f64, alpha: f64, two_sided: bool) -> f64 {
+    if !d.is_finite() {
+        return f64::NAN;
+    } else if d == 0.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = if two_sided { 0.5 * alpha } else { alpha };
+    let guess = 2.0 * ((s_norminv(1.0 - tail) + s_norminv(power)) / d).powi(2);
+    sample_size(
+        |n| ttest2_power(d.abs(), n, alpha, two_sided),
+        power,
+        alpha,
+        2.0,
+        guess,
+    )
+}
+
+// Power of a one-way ANOVA over k groups of n observations, for an effect size f
+// (Cohen's f, the deviation of the group means over the common standard deviation)
+#[wasm_bindgen]
+pub fn anova_power(f: f64, k: f64, n: f64, alpha: f64) -> f64 {
+    if !is_count(k) || k < 2.0 || n.is_nan() || n < 2.0 || !(alpha > 0.0 && alpha < 1.0) {
+        return f64::NAN;
+    }
+
+    let (v1, v2) = (k - 1.0, k * (n - 1.0));
+    1.0 - ncfcdf(finv(1.0 - alpha, v1, v2), v1, v2, f * f * k * n)
+}
+
+// Number of observations in each of the k groups for a one-way ANOVA to reach the power
+#[wasm_bindgen]
+pub fn anova_samplesize(f: f64, k: f64, power: f64, alpha: f64) -> f64 {
+    if !is_count(k) || k < 2.0 || !f.is_finite() {
+        return f64::NAN;
+    } else if f == 0.0 {
+        return f64::INFINITY;
+    }
+    sample_size(|n| anova_power(f, k, n, alpha), power, alpha, 2.0, 2.0)
+}
+
+//  ---------------------------------------------------------------
+//  DISCRETE DISTRIBUTIONS
+//  Binomial, Poisson, geometric, negative binomial, hypergeometric
+//  Probability mass, Cumulate, Quantile functions and random numbers
+//  ---------------------------------------------------------------
+
+// Whether x is a nonnegative integer
+fn is_count(x: f64) -> bool {
+    x >= 0.0 && x == x.floor() && x.is_finite()
+}
+
+// Logarithm of the binomial coefficient
+fn lnchoose(n: f64, k: f64) -> f64 {
+    lgamma(n + 1.0) - lgamma(k + 1.0) - lgamma(n - k + 1.0)
+}
+
+// Smallest integer x in [lower, upper] with cdf(x) >= p, searching outwards from a guess
+// and then bisecting. A relative slack of a few ulps on p absorbs the rounding of the cdf.
+fn discrete_quantile<F: Fn(f64) -> f64>(cdf: F, p: f64, lower: f64, upper: f64, guess: f64) -> f64 {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    } else if p == 0.0 {
+        return lower;
+    } else if p == 1.0 {
+        return upper;
+    }
+
+    let target = p * (1.0 - 64.0 * f64::EPSILON);
+    let start = if guess.is_finite() {
+        guess.floor().clamp(lower, upper)
+    } else {
+        lower
+    };
+
+    // Bracket: cdf(low) < target <= cdf(high), with low = lower - 1 standing for cdf = 0
+    let (mut low, mut high);
+    let mut step = 1.0;
+    if cdf(start) >= target {
+        high = start;
+        low = high - step;
+        while low >= lower && cdf(low) >= target {
+            high = low;
+            step *= 2.0;
+            low = high - step;
+        }
+        low = low.max(lower - 1.0);
+    } else {
+        low = start;
+        high = (low + step).min(upper);
+        while high < upper && cdf(high) < target {
+            low = high;
+            step *= 2.0;
+            high = (low + step).min(upper);
+        }
+    }
+
+    if high.is_infinite() {
+        return high;
+    }
+
+    while high - low > 1.0 {
+        let middle = (0.5 * (low + high)).floor();
+        if cdf(middle) >= target {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+
+    high
+}
+
+// Binomial distribution: x successes in n trials with probability p
+#[wasm_bindgen]
+pub fn binopdf(x: f64, n: f64, p: f64) -> f64 {
+    if !is_count(n) || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    } else if !is_count(x) || x > n {
+        return 0.0;
+    } else if p == 0.0 || p == 1.0 {
+        // Only one outcome is possible
+        return if x == n * p { 1.0 } else { 0.0 };
+    }
+
+    (lnchoose(n, x) + x * p.ln() + (n - x) * (-p).ln_1p()).exp()
+}
+
+// Cumulate binomial distribution, through the regularized incomplete beta
+#[wasm_bindgen]
+pub fn binocdf(x: f64, n: f64, p: f64) -> f64 {
+    if !is_count(n) || !(0.0..=1.0).contains(&p) || x.is_nan() {
+        return f64::NAN;
+    }
+
+    let k = x.floor();
+    if k < 0.0 {
+        0.0
+    } else if k >= n {
+        1.0
+    } else {
+        regincbet(1.0 - p, n - k, k + 1.0)
+    }
+}
+
+// Binomial distribution quantile
+#[wasm_bindgen]
+pub fn binoinv(y: f64, n: f64, p: f64) -> f64 {
+    if !is_count(n) || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    discrete_quantile(|k| binocdf(k, n, p), y, 0.0, n, n * p)
+}
+
+// Binomial random numbers
+#[wasm_bindgen]
+pub fn binornd(n: f64, p: f64, count: usize, seed: u64) -> Vec<f64> {
+    variates(count, seed, |rng| binoinv(rng.sample(Open01), n, p))
+}
+
+// Poisson distribution with mean lambda
+#[wasm_bindgen]
+pub fn poisspdf(x: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if !is_count(x) {
+        return 0.0;
+    } else if lambda == 0.0 {
+        return if x == 0.0 { 1.0 } else { 0.0 };
+    }
+
+    (x * lambda.ln() - lambda - lgamma(x + 1.0)).exp()
+}
+
+// Cumulate Poisson distribution, through the regularized upper incomplete gamma
+#[wasm_bindgen]
+pub fn poisscdf(x: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 || x.is_nan() {
+        return f64::NAN;
+    }
+
+    let k = x.floor();
+    if k < 0.0 {
+        0.0
+    } else if lambda == 0.0 || k == f64::INFINITY {
+        1.0
+    } else {
+        reguppincgamma(k + 1.0, lambda)
+    }
+}
+
+// Poisson distribution quantile
+#[wasm_bindgen]
+pub fn poissinv(y: f64, lambda: f64) -> f64 {
+    if lambda.is_nan() || lambda < 0.0 {
+        return f64::NAN;
+    } else if lambda == 0.0 && (0.0..=1.0).contains(&y) {
+        return 0.0;
+    }
+    discrete_quantile(|k| poisscdf(k, lambda), y, 0.0, f64::INFINITY, lambda)
+}
+
+// Poisson random numbers
+#[wasm_bindgen]
+pub fn poissrnd(lambda: f64, count: usize, seed: u64) -> Vec<f64> {
+    variates(count, seed, |rng| poissinv(rng.sample(Open01), lambda))
+}
+
+// Geometric distribution: x failures before the first success, like MATLAB's geopdf
+#[wasm_bindgen]
+pub fn geopdf(x: f64, p: f64) -> f64 {
+    if p.is_nan() || p <= 0.0 || p > 1.0 {
+        return f64::NAN;
+    } else if !is_count(x) {
+        return 0.0;
+    }
+    p * (x * (-p).ln_1p()).exp()
+}
+
+// Cumulate geometric distribution
+#[wasm_bindgen]
+pub fn geocdf(x: f64, p: f64) -> f64 {
+    if p.is_nan() || p <= 0.0 || p > 1.0 || x.is_nan() {
+        return f64::NAN;
+    }
+
+    let k = x.floor();
+    if k < 0.0 {
+        0.0
+    } else {
+        -((k + 1.0) * (-p).ln_1p()).exp_m1()
+    }
+}
+
+// Geometric distribution quantile
+#[wasm_bindgen]
+pub fn geoinv(y: f64, p: f64) -> f64 {
+    if p.is_nan() || p <= 0.0 || p > 1.0 {
+        return f64::NAN;
+    }
+    let guess = (-y).ln_1p() / (-p).ln_1p() - 1.0;
+    discrete_quantile(|k| geocdf(k, p), y, 0.0, f64::INFINITY, guess)
+}
+
+// Geometric random numbers
+#[wasm_bindgen]
+pub fn geornd(p: f64, count: usize, seed: u64) -> Vec<f64> {
+    variates(count, seed, |rng| geoinv(rng.sample(Open01), p))
+}
+
+// Negative binomial distribution: x failures before the r-th success, like MATLAB's nbinpdf
+#[wasm_bindgen]
+pub fn nbinpdf(x: f64, r: f64, p: f64) -> f64 {
+    if r.is_nan() || r <= 0.0 || p.is_nan() || p <= 0.0 || p > 1.0 {
+        return f64::NAN;
+    } else if !is_count(x) {
+        return 0.0;
+    } else if p == 1.0 {
+        return if x == 0.0 { 1.0 } else { 0.0 };
+    }
+
+    (lgamma(r + x) - lgamma(r) - lgamma(x + 1.0) + r * p.ln() + x * (-p).ln_1p()).exp()
+}
+
+// Cumulate negative binomial distribution, through the regularized incomplete beta
+#[wasm_bindgen]
+pub fn nbincdf(x: f64, r: f64, p: f64) -> f64 {
+    if r.is_nan() || r <= 0.0 || p.is_nan() || p <= 0.0 || p > 1.0 || x.is_nan() {
+        return f64::NAN;
+    }
+
+    let k = x.floor();
+    if k < 0.0 {
+        0.0
+    } else if k == f64::INFINITY {
+        1.0
+    } else {
+        regincbet(p, r, k + 1.0)
+    }
+}
+
+// Negative binomial distribution quantile
+#[wasm_bindgen]
+pub fn nbininv(y: f64, r: f64, p: f64) -> f64 {
+    if r.is_nan() || r <= 0.0 || p.is_nan() || p <= 0.0 || p > 1.0 {
+        return f64::NAN;
+    }
+    discrete_quantile(
+        |k| nbincdf(k, r, p),
+        y,
+        0.0,
+        f64::INFINITY,
+        r * (1.0 - p) / p,
+    )
+}
+
+// Negative binomial random numbers
+#[wasm_bindgen]
+pub fn nbinrnd(r: f64, p: f64, count: usize, seed: u64) -> Vec<f64> {
+    variates(count, seed, |rng| nbininv(rng.sample(Open01), r, p))
+}
+
+// Support of the hypergeometric distribution, None for invalid parameters
+fn hyge_support(m: f64, k: f64, n: f64) -> Option<(f64, f64)> {
+    if !is_count(m) || !is_count(k) || !is_count(n) || k > m || n > m {
+        return None;
+    }
+    Some((f64::max(0.0, n - (m - k)), f64::min(k, n)))
+}
+
+// Hypergeometric distribution: x successes in n draws without replacement
+// from a population of m items, k of which are successes (MATLAB's hygepdf(x, m, k, n))
+#[wasm_bindgen]
+pub fn hygepdf(x: f64, m: f64, k: f64, n: f64) -> f64 {
+    match hyge_support(m, k, n) {
+        None => f64::NAN,
+        Some((low, high)) if is_count(x) && x >= low && x <= high => {
+            (lnchoose(k, x) + lnchoose(m - k, n - x) - lnchoose(m, n)).exp()
+        }
+        Some(_) => 0.0,
+    }
+}
+
+// Cumulate hypergeometric distribution, summing the shorter tail of the mass function
+#[wasm_bindgen]
+pub fn hygecdf(x: f64, m: f64, k: f64, n: f64) -> f64 {
+    let (low, high) = match hyge_support(m, k, n) {
+        None => return f64::NAN,
+        Some(support) => support,
+    };
+
+    let x = x.floor();
+    if x.is_nan() {
+        f64::NAN
+    } else if x < low {
+        0.0
+    } else if x >= high {
+        1.0
+    } else if x < n * k / m {
+        let mut sum = 0.0;
+        let mut i = low;
+        while i <= x {
+            sum += hygepdf(i, m, k, n);
+            i += 1.0;
+        }
+        sum.min(1.0)
+    } else {
+        let mut tail = 0.0;
+        let mut i = x + 1.0;
+        while i <= high {
+            tail += hygepdf(i, m, k, n);
+            i += 1.0;
+        }
+        (1.0 - tail).max(0.0)
+    }
+}
+
+// Hypergeometric distribution quantile
+#[wasm_bindgen]
+pub fn hygeinv(y: f64, m: f64, k: f64, n: f64) -> f64 {
+    match hyge_support(m, k, n) {
+        None => f64::NAN,
+        Some((low, high)) => discrete_quantile(|x| hygecdf(x, m, k, n), y, low, high, n * k / m),
+    }
+}
+
+// Hypergeometric random numbers
+#[wasm_bindgen]
+pub fn hygernd(m: f64, k: f64, n: f64, count: usize, seed: u64) -> Vec<f64> {
+    variates(count, seed, |rng| hygeinv(rng.sample(Open01), m, k, n))
+}
+
+//  ---------------------------------------------------------
+//  RANDOM VARIATES
+//  Seeded generators, reproducible in WASM where there
+//  may be no OS entropy: the same seed gives the same values
+//  ---------------------------------------------------------
+
+// Standard normal variate (Marsaglia's polar form of the Box-Muller transform)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    loop {
+        let u = 2.0 * rng.gen::<f64>() - 1.0;
+        let v = 2.0 * rng.gen::<f64>() - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            return u * (-2.0 * s.ln() / s).sqrt();
+        }
+    }
+}
+
+// Gamma variate with unit scale (Marsaglia & Tsang's squeeze method)
+fn standard_gamma(shape: f64, rng: &mut StdRng) -> f64 {
+    if shape.is_nan() || shape <= 0.0 {
+        return f64::NAN;
+    }
+
+    // Boost shapes below one: G(a) = G(a + 1) U^(1/a)
+    if shape < 1.0 {
+        let u: f64 = rng.sample(Open01);
+        return standard_gamma(shape + 1.0, rng) * u.powf(shape.recip());
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = (9.0 * d).sqrt().recip();
+
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+
+        let u: f64 = rng.sample(Open01);
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+fn beta_variate(a: f64, b: f64, rng: &mut StdRng) -> f64 {
+    let x = standard_gamma(a, rng);
+    let y = standard_gamma(b, rng);
+    x / (x + y)
+}
+
+fn t_variate(v: f64, rng: &mut StdRng) -> f64 {
+    standard_normal(rng) / (2.0 * standard_gamma(0.5 * v, rng) / v).sqrt()
+}
+
+// n values drawn from a generator seeded with `seed`
+fn variates<F: FnMut(&mut StdRng) -> f64>(n: usize, seed: u64, mut generator: F) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n).map(|_| generator(&mut rng)).collect()
+}
+
+// Normal random numbers
+#[wasm_bindgen]
+pub fn normrnd(mu: f64, sigma: f64, n: usize, seed: u64) -> Vec<f64> {
+    variates(n, seed, |rng| mu + sigma * standard_normal(rng))
+}
+
+// Gamma random numbers, with shape a and scale b
+#[wasm_bindgen]
+pub fn gamrnd(a: f64, b: f64, n: usize, seed: u64) -> Vec<f64> {
+    variates(n, seed, |rng| b * standard_gamma(a, rng))
+}
+
+// Beta random numbers
+#[wasm_bindgen]
+pub fn betarnd(a: f64, b: f64, n: usize, seed: u64) -> Vec<f64> {
+    variates(n, seed, |rng| beta_variate(a, b, rng))
+}
+
+// Student's t random numbers
+#[wasm_bindgen]
+pub fn trnd(v: f64, n: usize, seed: u64) -> Vec<f64> {
+    variates(n, seed, |rng| t_variate(v, rng))
+}
+
+// Chi-squared random numbers
+#[wasm_bindgen]
+pub fn chi2rnd(k: f64, n: usize, seed: u64) -> Vec<f64> {
+    variates(n, seed, |rng| 2.0 * standard_gamma(0.5 * k, rng))
+}
+
+//  ------------------------------------------------------------
+//  DISTRIBUTION OBJECTS
+//  A common interface over the distributions above, with the
+//  parameters stored once instead of passed to every function
+//  ------------------------------------------------------------
+
+pub trait ContinuousDistribution {
+    fn pdf(&self, x: f64) -> f64;
+
+    fn logpdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64;
+
+    // Survival function 1 - cdf(x), to be overridden where the tail can be computed directly
+    fn sf(&self, x: f64) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    fn quantile(&self, p: f64) -> f64;
+
+    fn mean(&self) -> f64;
+
+    fn variance(&self) -> f64;
+
+    fn skewness(&self) -> f64;
+
+    // Excess kurtosis, 0 for the normal distribution
+    fn kurtosis(&self) -> f64;
+
+    // Differential entropy, in nats
+    fn entropy(&self) -> f64;
+
+    // One random variate, by inversion of the cdf unless a faster method is available
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.quantile(rng.sample(Open01))
+    }
+}
+
+// Exposes the ContinuousDistribution methods of a type to JS
+macro_rules! wasm_distribution {
+    ($name:ident) => {
+        #[wasm_bindgen]
+        impl $name {
+            #[wasm_bindgen(js_name = pdf)]
+            pub fn js_pdf(&self, x: f64) -> f64 {
+                self.pdf(x)
+            }
+
+            #[wasm_bindgen(js_name = logpdf)]
+            pub fn js_logpdf(&self, x: f64) -> f64 {
+                self.logpdf(x)
+            }
+
+            #[wasm_bindgen(js_name = cdf)]
+            pub fn js_cdf(&self, x: f64) -> f64 {
+                self.cdf(x)
+            }
+
+            #[wasm_bindgen(js_name = sf)]
+            pub fn js_sf(&self, x: f64) -> f64 {
+                self.sf(x)
+            }
+
+            #[wasm_bindgen(js_name = quantile)]
+            pub fn js_quantile(&self, p: f64) -> f64 {
+                self.quantile(p)
+            }
+
+            #[wasm_bindgen(js_name = mean)]
+            pub fn js_mean(&self) -> f64 {
+                self.mean()
+            }
+
+            #[wasm_bindgen(js_name = variance)]
+            pub fn js_variance(&self) -> f64 {
+                self.variance()
+            }
+
+            #[wasm_bindgen(js_name = skewness)]
+            pub fn js_skewness(&self) -> f64 {
+                self.skewness()
+            }
+
+            #[wasm_bindgen(js_name = kurtosis)]
+            pub fn js_kurtosis(&self) -> f64 {
+                self.kurtosis()
+            }
+
+            #[wasm_bindgen(js_name = entropy)]
+            pub fn js_entropy(&self) -> f64 {
+                self.entropy()
+            }
+
+            // n random variates; the same seed always gives the same values
+            #[wasm_bindgen(js_name = sample)]
+            pub fn js_sample(&self, n: usize, seed: u64) -> Vec<f64> {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..n).map(|_| self.sample(&mut rng)).collect()
+            }
+        }
+    };
+}
+
+// Normal distribution with mean mu and standard deviation sigma
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Normal {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+#[wasm_bindgen]
+impl Normal {
+    #[wasm_bindgen(constructor)]
+    pub fn new(mu: f64, sigma: f64) -> Normal {
+        Normal { mu, sigma }
+    }
+}
+
+impl ContinuousDistribution for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        normpdf(x, self.mu, self.sigma)
+    }
+
+    fn logpdf(&self, x: f64) -> f64 {
+        lognormpdf(x, self.mu, self.sigma)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        normcdf(x, self.mu, self.sigma)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        s_normcdf((self.mu - x) / self.sigma)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        norminv(p, self.mu, self.sigma)
+    }
+
+    fn mean(&self) -> f64 {
+        self.mu
+    }
+
+    fn variance(&self) -> f64 {
+        self.sigma * self.sigma
+    }
+
+    fn skewness(&self) -> f64 {
+        0.0
+    }
+
+    fn kurtosis(&self) -> f64 {
+        0.0
+    }
+
+    fn entropy(&self) -> f64 {
+        0.5 * (2.0 * PI * E * self.sigma * self.sigma).ln()
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.mu + self.sigma * standard_normal(rng)
+    }
+}
+
+wasm_distribution!(Normal);
+
+// Gamma distribution with shape and scale parameters, like gampdf
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Gamma {
+    pub shape: f64,
+    pub scale: f64,
+}
+
+#[wasm_bindgen]
+impl Gamma {
+    #[wasm_bindgen(constructor)]
+    pub fn new(shape: f64, scale: f64) -> Gamma {
+        Gamma { shape, scale }
+    }
+}
+
+impl ContinuousDistribution for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        gammapdf(x, self.shape, self.scale)
+    }
+
+    fn logpdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        (self.shape - 1.0) * x.ln()
+            - x / self.scale
+            - self.shape * self.scale.ln()
+            - lgamma(self.shape)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        gammacdf(x, self.shape, self.scale)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        reguppincgamma(self.shape, x / self.scale)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        gammainv(p, self.shape, self.scale)
+    }
+
+    fn mean(&self) -> f64 {
+        self.shape * self.scale
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape * self.scale * self.scale
+    }
+
+    fn skewness(&self) -> f64 {
+        2.0 / self.shape.sqrt()
+    }
+
+    fn kurtosis(&self) -> f64 {
+        6.0 / self.shape
+    }
+
+    fn entropy(&self) -> f64 {
+        let k = self.shape;
+        k + self.scale.ln() + lgamma(k) + (1.0 - k) * digamma(k)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        self.scale * standard_gamma(self.shape, rng)
+    }
+}
+
+wasm_distribution!(Gamma);
+
+// Beta distribution on [0, 1]
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Beta {
+    pub a: f64,
+    pub b: f64,
+}
+
+#[wasm_bindgen]
+impl Beta {
+    #[wasm_bindgen(constructor)]
+    pub fn new(a: f64, b: f64) -> Beta {
+        Beta { a, b }
+    }
+}
+
+impl ContinuousDistribution for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return 0.0;
+        }
+        self.logpdf(x).exp()
+    }
+
+    fn logpdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        (self.a - 1.0) * x.ln() + (self.b - 1.0) * (1.0 - x).ln() - betaln(self.a, self.b)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        betacdf(x, self.a, self.b)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        betacdf(1.0 - x, self.b, self.a)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        betainv(p, self.a, self.b)
+    }
+
+    fn mean(&self) -> f64 {
+        self.a / (self.a + self.b)
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b) = (self.a, self.b);
+        a * b / ((a + b).powi(2) * (a + b + 1.0))
+    }
+
+    fn skewness(&self) -> f64 {
+        let (a, b) = (self.a, self.b);
+        2.0 * (b - a) * (a + b + 1.0).sqrt() / ((a + b + 2.0) * (a * b).sqrt())
+    }
+
+    fn kurtosis(&self) -> f64 {
+        let (a, b) = (self.a, self.b);
+        6.0 * ((a - b).powi(2) * (a + b + 1.0) - a * b * (a + b + 2.0))
+            / (a * b * (a + b + 2.0) * (a + b + 3.0))
+    }
+
+    fn entropy(&self) -> f64 {
+        let (a, b) = (self.a, self.b);
+        betaln(a, b) - (a - 1.0) * digamma(a) - (b - 1.0) * digamma(b)
+            + (a + b - 2.0) * digamma(a + b)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        beta_variate(self.a, self.b, rng)
+    }
+}
+
+wasm_distribution!(Beta);
+
+// Student's t distribution with v degrees of freedom
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct StudentT {
+    pub v: f64,
+}
+
+#[wasm_bindgen]
+impl StudentT {
+    #[wasm_bindgen(constructor)]
+    pub fn new(v: f64) -> StudentT {
+        StudentT { v }
+    }
+}
+
+impl ContinuousDistribution for StudentT {
+    fn pdf(&self, x: f64) -> f64 {
+        self.logpdf(x).exp()
+    }
+
+    // In log space, so that large degrees of freedom don't overflow the gamma functions
+    fn logpdf(&self, x: f64) -> f64 {
+        let v = self.v;
+        lgamma(0.5 * (v + 1.0))
+            - lgamma(0.5 * v)
+            - 0.5 * (v * PI).ln()
+            - 0.5 * (v + 1.0) * (x * x / v).ln_1p()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        tcdf(x, self.v)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        tcdf(-x, self.v)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        tinv(p, self.v)
+    }
+
+    fn mean(&self) -> f64 {
+        if self.v > 1.0 {
+            0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        if self.v > 2.0 {
+            self.v / (self.v - 2.0)
+        } else if self.v > 1.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn skewness(&self) -> f64 {
+        if self.v > 3.0 {
+            0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn kurtosis(&self) -> f64 {
+        if self.v > 4.0 {
+            6.0 / (self.v - 4.0)
+        } else if self.v > 2.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn entropy(&self) -> f64 {
+        let v = self.v;
+        0.5 * (v + 1.0) * (digamma(0.5 * (v + 1.0)) - digamma(0.5 * v))
+            + 0.5 * v.ln()
+            + betaln(0.5 * v, 0.5)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        t_variate(self.v, rng)
+    }
+}
+
+wasm_distribution!(StudentT);
+
+// Chi-squared distribution with k degrees of freedom, a gamma distribution with shape k/2 and scale 2
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ChiSquared {
+    pub k: f64,
+}
+
+#[wasm_bindgen]
+impl ChiSquared {
+    #[wasm_bindgen(constructor)]
+    pub fn new(k: f64) -> ChiSquared {
+        ChiSquared { k }
+    }
+
+    fn as_gamma(&self) -> Gamma {
+        Gamma::new(0.5 * self.k, 2.0)
+    }
+}
+
+impl ContinuousDistribution for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        chi2pdf(x, self.k)
+    }
+
+    fn logpdf(&self, x: f64) -> f64 {
+        self.as_gamma().logpdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        chi2cdf(x, self.k)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.as_gamma().sf(x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        chi2inv(p, self.k)
+    }
+
+    fn mean(&self) -> f64 {
+        self.k
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * self.k
+    }
+
+    fn skewness(&self) -> f64 {
+        (8.0 / self.k).sqrt()
+    }
+
+    fn kurtosis(&self) -> f64 {
+        12.0 / self.k
+    }
+
+    fn entropy(&self) -> f64 {
+        self.as_gamma().entropy()
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        2.0 * standard_gamma(0.5 * self.k, rng)
+    }
+}
+
+wasm_distribution!(ChiSquared);
+
+//  ------------------------------------------------------------
+//  MULTIVARIATE NORMAL DISTRIBUTION
+//  Density and sampling through the Cholesky factor of the
+//  covariance, cumulate distribution by Genz's algorithm
+//  ------------------------------------------------------------
+
+// Absolute error target (the default of MATLAB's mvncdf) and point budget of the quasi-Monte Carlo
+// integration in Genz's algorithm
+const GENZ_TOLERANCE: f64 = 1e-4;
+const GENZ_MAX_POINTS: usize = 1 << 20;
+const GENZ_SHIFTS: usize = 12;
+
+// Standard bivariate normal cumulate distribution P(X <= h, Y <= k) with correlation rho,
+// through the integral over the arcsine of the correlation (Sheppard's formula)
+#[wasm_bindgen]
+pub fn bvncdf(h: f64, k: f64, rho: f64) -> f64 {
+    if h.is_nan() || k.is_nan() || rho.is_nan() || rho.abs() > 1.0 {
+        return f64::NAN;
+    } else if h == f64::NEG_INFINITY || k == f64::NEG_INFINITY {
+        return 0.0;
+    } else if h == f64::INFINITY {
+        return s_normcdf(k);
+    } else if k == f64::INFINITY {
+        return s_normcdf(h);
+    } else if rho == 1.0 {
+        return s_normcdf(h.min(k));
+    } else if rho == -1.0 {
+        return (s_normcdf(h) - s_normcdf(-k)).max(0.0);
+    }
+
+    let integrand = |t: f64| -> f64 {
+        let cosine = t.cos();
+        (-(h * h + k * k - 2.0 * h * k * t.sin()) / (2.0 * cosine * cosine)).exp()
+    };
+
+    let cdf =
+        s_normcdf(h) * s_normcdf(k) + integrate(integrand, 0.0, rho.asin(), 2000) / (2.0 * PI);
+    cdf.clamp(0.0, 1.0)
+}
+
+// The first n prime numbers, whose square roots generate the Richtmyer lattice
+fn primes(n: usize) -> Vec<u64> {
+    let mut primes: Vec<u64> = Vec::with_capacity(n);
+    let mut candidate = 2;
+
+    while primes.len() < n {
+        if primes
+            .iter()
+            .take_while(|&&p| p * p <= candidate)
+            .all(|&p| candidate % p != 0)
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+
+    primes
+}
+
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    covariance: SquareMatrix,
+    // Lower triangular, with L L^T equal to the covariance
+    cholesky: SquareMatrix,
+}
+
+impl MultivariateNormal {
+    pub(crate) fn new(
+        mean: Vec<f64>,
+        covariance: SquareMatrix,
+    ) -> Result<MultivariateNormal, Error> {
+        if mean.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The distribution needs at least one dimension",
+            ));
+        } else if mean.len() != covariance.size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Mismatched mean and covariance dimensions",
+            ));
+        } else if covariance.matrix.len() != covariance.size * covariance.size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The covariance entries don't match its size",
+            ));
+        }
+
+        let cholesky = covariance.cholesky()?;
+        Ok(MultivariateNormal {
+            mean,
+            covariance,
+            cholesky,
+        })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.mean.len()
+    }
+
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    // Solution of L z = x - mean by forward substitution
+    fn whiten(&self, x: &[f64]) -> Vec<f64> {
+        let n = self.dimension();
+        let mut z = vec![0.0; n];
+
+        for i in 0..n {
+            let s: f64 = (0..i).map(|j| self.cholesky.get(i + 1, j + 1) * z[j]).sum();
+            z[i] = (x[i] - self.mean[i] - s) / self.cholesky.get(i + 1, i + 1);
+        }
+
+        z
+    }
+
+    pub fn logpdf(&self, x: &[f64]) -> f64 {
+        if x.len() != self.dimension() {
+            return f64::NAN;
+        }
+
+        let n = self.dimension();
+        let ln_det: f64 = (1..=n).map(|i| self.cholesky.get(i, i).ln()).sum();
+        let distance: f64 = self.whiten(x).iter().map(|z| z * z).sum();
+
+        -0.5 * (n as f64 * (2.0 * PI).ln() + distance) - ln_det
+    }
+
+    pub fn pdf(&self, x: &[f64]) -> f64 {
+        self.logpdf(x).exp()
+    }
+
+    // One variate: the mean plus the Cholesky factor applied to independent standard normals
+    pub fn sample(&self, rng: &mut StdRng) -> Vec<f64> {
+        let z: Vec<f64> = (0..self.dimension())
+            .map(|_| standard_normal(rng))
+            .collect();
+        self.cholesky
+            .mul_vec(&z)
+            .iter()
+            .zip(&self.mean)
+            .map(|(y, mu)| y + mu)
+            .collect()
+    }
+
+    // Cumulate distribution P(X <= x)
+    pub fn cdf(&self, x: &[f64]) -> f64 {
+        let lower = vec![f64::NEG_INFINITY; self.dimension()];
+        self.probability(&lower, x)
+    }
+
+    // Probability of the box lower <= X <= upper
+    pub fn probability(&self, lower: &[f64], upper: &[f64]) -> f64 {
+        let n = self.dimension();
+        if lower.len() != n || upper.len() != n || lower.iter().chain(upper).any(|x| x.is_nan()) {
+            return f64::NAN;
+        } else if lower.iter().zip(upper).any(|(a, b)| a >= b) {
+            return 0.0;
+        }
+
+        match n {
+            1 => {
+                let sigma = self.cholesky.get(1, 1);
+                normcdf(upper[0], self.mean[0], sigma) - normcdf(lower[0], self.mean[0], sigma)
+            }
+            2 => {
+                // Inclusion-exclusion over the corners of the rectangle
+                let (s1, s2) = (
+                    self.covariance.get(1, 1).sqrt(),
+                    self.covariance.get(2, 2).sqrt(),
+                );
+                let rho = self.covariance.get(2, 1) / (s1 * s2);
+                let corner =
+                    |x: f64, y: f64| bvncdf((x - self.mean[0]) / s1, (y - self.mean[1]) / s2, rho);
+
+                (corner(upper[0], upper[1])
+                    - corner(lower[0], upper[1])
+                    - corner(upper[0], lower[1])
+                    + corner(lower[0], lower[1]))
+                .clamp(0.0, 1.0)
+            }
+            _ => self.genz(lower, upper),
+        }
+    }
+
+    // Genz's separation of variables: the box probability becomes an integral over the unit
+    // hypercube of dimension n - 1, estimated on randomly shifted Richtmyer lattices
+    fn genz(&self, lower: &[f64], upper: &[f64]) -> f64 {
+        let n = self.dimension();
+        let a: Vec<f64> = lower.iter().zip(&self.mean).map(|(x, mu)| x - mu).collect();
+        let b: Vec<f64> = upper.iter().zip(&self.mean).map(|(x, mu)| x - mu).collect();
+        let l = |i: usize, j: usize| -> f64 { self.cholesky.get(i + 1, j + 1) };
+
+        let d1 = s_normcdf(a[0] / l(0, 0));
+        let e1 = s_normcdf(b[0] / l(0, 0));
+
+        // Integrand at a point w of the unit hypercube
+        let integrand = |w: &[f64], y: &mut [f64]| -> f64 {
+            let (mut d, mut e) = (d1, e1);
+            let mut f = e - d;
+
+            for i in 1..n {
+                if f == 0.0 {
+                    break;
+                }
+
+                let u = (d + w[i - 1] * (e - d)).clamp(1e-16, 1.0 - 1e-16);
+                y[i - 1] = s_norminv(u);
+
+                let s: f64 = (0..i).map(|j| l(i, j) * y[j]).sum();
+                d = s_normcdf((a[i] - s) / l(i, i));
+                e = s_normcdf((b[i] - s) / l(i, i));
+                f *= e - d;
+            }
+
+            f
+        };
+
+        let generators: Vec<f64> = primes(n - 1)
+            .iter()
+            .map(|&p| (p as f64).sqrt().fract())
+            .collect();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut w = vec![0.0; n - 1];
+        let mut y = vec![0.0; n];
+
+        let mut points = 512;
+        let mut used = 0;
+        let mut estimate;
+
+        loop {
+            let mut sum = 0.0;
+            let mut sum_squares = 0.0;
+
+            for _shift in 0..GENZ_SHIFTS {
+                let shift: Vec<f64> = (0..n - 1).map(|_| rng.gen::<f64>()).collect();
+                let mut mean = 0.0;
+
+                for k in 1..=points {
+                    for j in 0..n - 1 {
+                        // Tent transform, periodizing the integrand
+                        let x = (k as f64 * generators[j] + shift[j]).fract();
+                        w[j] = (2.0 * x - 1.0).abs();
+                    }
+                    mean += integrand(&w, &mut y);
+                }
+
+                mean /= points as f64;
+                sum += mean;
+                sum_squares += mean * mean;
+            }
+
+            used += points * GENZ_SHIFTS;
+            let shifts = GENZ_SHIFTS as f64;
+            estimate = sum / shifts;
+            let variance = ((sum_squares - shifts * estimate * estimate) / (shifts - 1.0)).max(0.0);
+            let error = 3.0 * (variance / shifts).sqrt();
+
+            if error < GENZ_TOLERANCE || used >= GENZ_MAX_POINTS {
+                break;
+            }
+            points *= 2;
+        }
+
+        estimate.clamp(0.0, 1.0)
+    }
+}
+
+#[wasm_bindgen]
+impl MultivariateNormal {
+    // The covariance is a JSON SquareMatrix, as in determinant
+    #[wasm_bindgen(constructor)]
+    pub fn js_new(mean: Vec<f64>, covariance: String) -> Result<MultivariateNormal, JsValue> {
+        let covariance: SquareMatrix =
+            serde_json::from_str(&covariance).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        MultivariateNormal::new(mean, covariance).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = pdf)]
+    pub fn js_pdf(&self, x: Vec<f64>) -> f64 {
+        self.pdf(&x)
+    }
+
+    #[wasm_bindgen(js_name = logpdf)]
+    pub fn js_logpdf(&self, x: Vec<f64>) -> f64 {
+        self.logpdf(&x)
+    }
+
+    #[wasm_bindgen(js_name = cdf)]
+    pub fn js_cdf(&self, x: Vec<f64>) -> f64 {
+        self.cdf(&x)
+    }
+
+    #[wasm_bindgen(js_name = probability)]
+    pub fn js_probability(&self, lower: Vec<f64>, upper: Vec<f64>) -> f64 {
+        self.probability(&lower, &upper)
+    }
+
+    // n variates, concatenated in a single array of n * dimension values
+    #[wasm_bindgen(js_name = sample)]
+    pub fn js_sample(&self, n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).flat_map(|_| self.sample(&mut rng)).collect()
+    }
+}
+
+//  ------------------------------------------------------------
+//  DESCRIPTIVE STATISTICS
+//  Sample statistics of data arrays (Float64Array in WASM):
+//  location, spread, shape and order statistics
+//  ------------------------------------------------------------
+
+// Mean and sums of powers of the deviations from the mean, updated one value at a time
+// (Welford's algorithm, extended by Terriberry to the third and fourth powers)
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+struct Moments {
+    n: f64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    fn of(x: &[f64]) -> Moments {
+        let mut moments = Moments::default();
+        for &value in x {
+            moments.push(value);
+        }
+        moments
+    }
+
+    fn push(&mut self, x: f64) {
+        let n1 = self.n;
+        self.n += 1.0;
+        let n = self.n;
+
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n1;
+
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+
+    // Moments of the union of two samples (Chan's and Pebay's pairwise formulas)
+    fn merge(&mut self, other: &Moments) {
+        if other.n == 0.0 {
+            return;
+        } else if self.n == 0.0 {
+            *self = *other;
+            return;
+        }
+
+        let (na, nb) = (self.n, other.n);
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let delta2 = delta * delta;
+
+        let m4 = self.m4
+            + other.m4
+            + delta2 * delta2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * delta2 * (na * na * other.m2 + nb * nb * self.m2) / (n * n)
+            + 4.0 * delta * (na * other.m3 - nb * self.m3) / n;
+        let m3 = self.m3
+            + other.m3
+            + delta2 * delta * na * nb * (na - nb) / (n * n)
+            + 3.0 * delta * (na * other.m2 - nb * self.m2) / n;
+        let m2 = self.m2 + other.m2 + delta2 * na * nb / n;
+
+        *self = Moments {
+            n,
+            mean: self.mean + delta * nb / n,
+            m2,
+            m3,
+            m4,
+        };
+    }
+
+    fn mean(&self) -> f64 {
+        if self.n > 0.0 {
+            self.mean
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn variance(&self, ddof: f64) -> f64 {
+        if self.n - ddof > 0.0 {
+            self.m2 / (self.n - ddof)
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn skewness(&self) -> f64 {
+        self.n.sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+
+    fn kurtosis(&self) -> f64 {
+        self.n * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+}
+
+// Sorted copy of the data, None if it is empty or has NaNs
+fn ordered(x: &[f64]) -> Option<Vec<f64>> {
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return None;
+    }
+
+    let mut sorted = x.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Some(sorted)
+}
+
+// Quantile of sorted data with the method of the given Hyndman and Fan type (1 to 9)
+fn sorted_quantile(sorted: &[f64], p: f64, method: u8) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+
+    let n = sorted.len() as f64;
+    // 1-based order statistic, clamped to the data
+    let at = |j: f64| -> f64 { sorted[(j.clamp(1.0, n) - 1.0) as usize] };
+    // Tolerance on the position, so that p = 0.3 with 10 values lands on the 3rd one
+    let fuzz = 4.0 * f64::EPSILON * n;
+
+    match method {
+        // Discontinuous sample quantiles
+        1..=3 => {
+            let h = if method == 3 { n * p - 0.5 } else { n * p };
+            let j = (h + fuzz).floor();
+            let on_point = (h - j).abs() <= fuzz;
+
+            match method {
+                1 if on_point => at(j),
+                2 if on_point => 0.5 * (at(j) + at(j + 1.0)),
+                3 if on_point && j % 2.0 == 0.0 => at(j),
+                _ => at(j + 1.0),
+            }
+        }
+        // Piecewise linear interpolation between order statistics
+        4..=9 => {
+            let m = match method {
+                4 => 0.0,
+                5 => 0.5,
+                6 => p,
+                7 => 1.0 - p,
+                8 => (p + 1.0) / 3.0,
+                _ => 0.25 * p + 0.375,
+            };
+            let h = n * p + m;
+            let j = (h + fuzz).floor();
+            let g = if (h - j).abs() <= fuzz { 0.0 } else { h - j };
+
+            (1.0 - g) * at(j) + g * at(j + 1.0)
+        }
+        _ => f64::NAN,
+    }
+}
+
+// Arithmetic mean
+#[wasm_bindgen]
+pub fn mean(x: &[f64]) -> f64 {
+    Moments::of(x).mean()
+}
+
+// Variance with ddof delta degrees of freedom: 1 for the sample variance, 0 for the population one
+#[wasm_bindgen]
+pub fn variance(x: &[f64], ddof: f64) -> f64 {
+    Moments::of(x).variance(ddof)
+}
+
+// Standard deviation with ddof delta degrees of freedom
+#[wasm_bindgen]
+pub fn stdev(x: &[f64], ddof: f64) -> f64 {
+    variance(x, ddof).sqrt()
+}
+
+// Sample skewness (biased, like MATLAB's skewness)
+#[wasm_bindgen]
+pub fn skewness(x: &[f64]) -> f64 {
+    Moments::of(x).skewness()
+}
+
+// Sample excess kurtosis (biased), 0 for normal data unlike MATLAB's kurtosis
+#[wasm_bindgen]
+pub fn kurtosis(x: &[f64]) -> f64 {
+    Moments::of(x).kurtosis()
+}
+
+#[wasm_bindgen]
+pub fn median(x: &[f64]) -> f64 {
+    match ordered(x) {
+        Some(sorted) => sorted_quantile(&sorted, 0.5, 2),
+        None => f64::NAN,
+    }
+}
+
+// Most frequent value, the smallest one in case of ties
+#[wasm_bindgen]
+pub fn mode(x: &[f64]) -> f64 {
+    let sorted = match ordered(x) {
+        Some(sorted) => sorted,
+        None => return f64::NAN,
+    };
+
+    let mut best = (sorted[0], 0);
+    let mut i = 0;
+    while i < sorted.len() {
+        let count = sorted[i..].iter().take_while(|&&v| v == sorted[i]).count();
+        if count > best.1 {
+            best = (sorted[i], count);
+        }
+        i += count;
+    }
+
+    best.0
+}
+
+// Quantile of order p with the Hyndman and Fan method 1 to 9 (7 is the default in R and NumPy,
+// 5 is MATLAB's quantile, 6 is Excel's PERCENTILE.EXC)
+#[wasm_bindgen]
+pub fn quantile(x: &[f64], p: f64, method: u8) -> f64 {
+    match ordered(x) {
+        Some(sorted) => sorted_quantile(&sorted, p, method),
+        None => f64::NAN,
+    }
+}
+
+// Percentile p (between 0 and 100) with the Hyndman and Fan method 1 to 9
+#[wasm_bindgen]
+pub fn percentile(x: &[f64], p: f64, method: u8) -> f64 {
+    quantile(x, p / 100.0, method)
+}
+
+// Interquartile range, with method 7 quartiles
+#[wasm_bindgen]
+pub fn iqr(x: &[f64]) -> f64 {
+    match ordered(x) {
+        Some(sorted) => sorted_quantile(&sorted, 0.75, 7) - sorted_quantile(&sorted, 0.25, 7),
+        None => f64::NAN,
+    }
+}
+
+// Median absolute deviation from the median (unscaled)
+#[wasm_bindgen]
+pub fn mad(x: &[f64]) -> f64 {
+    let center = median(x);
+    let deviations: Vec<f64> = x.iter().map(|v| (v - center).abs()).collect();
+    median(&deviations)
+}
+
+#[wasm_bindgen]
+pub fn min(x: &[f64]) -> f64 {
+    match ordered(x) {
+        Some(sorted) => sorted[0],
+        None => f64::NAN,
+    }
+}
+
+#[wasm_bindgen]
+pub fn max(x: &[f64]) -> f64 {
+    match ordered(x) {
+        Some(sorted) => sorted[sorted.len() - 1],
+        None => f64::NAN,
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+pub struct FiveNumberSummary {
+    pub minimum: f64,
+    pub first_quartile: f64,
+    pub median: f64,
+    pub third_quartile: f64,
+    pub maximum: f64,
+}
+
+// Minimum, quartiles (method 7) and maximum
+pub fn five_number_summary(x: &[f64]) -> Option<FiveNumberSummary> {
+    let sorted = ordered(x)?;
+
+    Some(FiveNumberSummary {
+        minimum: sorted[0],
+        first_quartile: sorted_quantile(&sorted, 0.25, 7),
+        median: sorted_quantile(&sorted, 0.5, 7),
+        third_quartile: sorted_quantile(&sorted, 0.75, 7),
+        maximum: sorted[sorted.len() - 1],
+    })
+}
+
+// Five-number summary as a JSON object, null for empty data or data with NaNs
+#[wasm_bindgen]
+pub fn summarize(x: &[f64]) -> String {
+    serde_json::to_string(&five_number_summary(x)).expect("Summaries are always serializable")
+}
+
+//  ------------------------------------------------------------
+//  STREAMING STATISTICS
+//  Accumulators updated one value at a time, in constant memory,
+//  mergeable across chunks of data processed in parallel
+//  ------------------------------------------------------------
+
+const DEFAULT_COMPRESSION: f64 = 100.0;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "PascalCase")]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+// Merging t-digest (Dunning): a sorted list of centroids, small in the tails and large in the
+// middle, so that quantile estimates are most accurate for extreme orders. With the default
+// compression of 100 it keeps at most a few hundred centroids.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+}
+
+impl TDigest {
+    fn new(compression: f64) -> TDigest {
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, x: f64, weight: f64) {
+        self.buffer.push(Centroid { mean: x, weight });
+        if self.buffer.len() as f64 >= 5.0 * self.compression {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, other: &TDigest) {
+        for centroid in other.centroids.iter().chain(&other.buffer) {
+            self.push(centroid.mean, centroid.weight);
+        }
+        self.compress();
+    }
+
+    // Scale function k1 mapping quantiles to centroid indices, and its inverse:
+    // a centroid can span at most one unit of k
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / PI * (2.0 * q - 1.0).asin()
+    }
+
+    fn inverse_scale(&self, k: f64) -> f64 {
+        if k >= 0.5 * self.compression {
+            1.0
+        } else {
+            0.5 * ((PI * k / self.compression).sin() + 1.0)
+        }
+    }
+
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let mut all: Vec<Centroid> = self
+            .centroids
+            .drain(..)
+            .chain(self.buffer.drain(..))
+            .collect();
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut merged: Vec<Centroid> = Vec::new();
+        let mut before = 0.0;
+        let mut current = all[0];
+        let mut limit = self.inverse_scale(self.scale(0.0) + 1.0);
+
+        for next in all.into_iter().skip(1) {
+            let weight = current.weight + next.weight;
+            if (before + weight) / total <= limit {
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                limit = self.inverse_scale(self.scale(before / total) + 1.0);
+                current = next;
+            }
+        }
+        merged.push(current);
+
+        self.centroids = merged;
+    }
+
+    // Interpolates between the centroid means, pinned at the exact extremes of the data
+    fn quantile(&mut self, p: f64, min: f64, max: f64) -> f64 {
+        self.compress();
+        if !(0.0..=1.0).contains(&p) || self.centroids.is_empty() {
+            return f64::NAN;
+        } else if self.centroids.len() == 1 {
+            return self.centroids[0].mean;
+        }
+
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let index = p * total;
+
+        let first = self.centroids[0];
+        if index < 0.5 * first.weight {
+            return min + (first.mean - min) * index / (0.5 * first.weight);
+        }
+
+        let last = self.centroids[self.centroids.len() - 1];
+        if index > total - 0.5 * last.weight {
+            return last.mean
+                + (max - last.mean) * (index - total + 0.5 * last.weight) / (0.5 * last.weight);
+        }
+
+        let mut center = 0.5 * first.weight;
+        for pair in self.centroids.windows(2) {
+            let next_center = center + 0.5 * (pair[0].weight + pair[1].weight);
+            if index <= next_center {
+                let t = (index - center) / (next_center - center);
+                return pair[0].mean + t * (pair[1].mean - pair[0].mean);
+            }
+            center = next_center;
+        }
+
+        last.mean
+    }
+}
+
+// Running count, mean, variance, skewness, kurtosis, extremes and approximate quantiles
+#[wasm_bindgen]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Accumulator {
+    moments: Moments,
+    // None until a value is pushed, as JSON has no NaN
+    min: Option<f64>,
+    max: Option<f64>,
+    digest: TDigest,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Accumulator::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Accumulator {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Accumulator {
+        Accumulator::with_compression(DEFAULT_COMPRESSION)
+    }
+
+    // Higher compressions give more accurate quantiles for more memory
+    pub fn with_compression(compression: f64) -> Accumulator {
+        Accumulator {
+            moments: Moments::default(),
+            min: None,
+            max: None,
+            digest: TDigest::new(compression.max(10.0)),
+        }
+    }
+
+    // NaNs are ignored
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+
+        self.moments.push(x);
+        self.min = Some(self.min.map_or(x, |min| min.min(x)));
+        self.max = Some(self.max.map_or(x, |max| max.max(x)));
+        self.digest.push(x, 1.0);
+    }
+
+    pub fn extend(&mut self, x: &[f64]) {
+        for &value in x {
+            self.push(value);
+        }
+    }
+
+    // Adds the values seen by another accumulator, as if they had been pushed to this one
+    pub fn merge(&mut self, other: &Accumulator) {
+        self.moments.merge(&other.moments);
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.digest.merge(&other.digest);
+    }
+
+    pub fn count(&self) -> f64 {
+        self.moments.n
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.moments.mean()
+    }
+
+    pub fn variance(&self, ddof: f64) -> f64 {
+        self.moments.variance(ddof)
+    }
+
+    pub fn stdev(&self, ddof: f64) -> f64 {
+        self.moments.variance(ddof).sqrt()
+    }
+
+    pub fn skewness(&self) -> f64 {
+        self.moments.skewness()
+    }
+
+    pub fn kurtosis(&self) -> f64 {
+        self.moments.kurtosis()
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min.unwrap_or(f64::NAN)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max.unwrap_or(f64::NAN)
+    }
+
+    // Approximate quantile of order p, from the t-digest
+    pub fn quantile(&mut self, p: f64) -> f64 {
+        self.digest.quantile(p, self.min(), self.max())
+    }
+
+    pub fn median(&mut self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    // JSON state, to send an accumulator from a worker and merge it elsewhere
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Accumulators are always serializable")
+    }
+
+    pub fn from_json(json: String) -> Result<Accumulator, JsValue> {
+        serde_json::from_str(&json).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+//  ------------------------------------------------------------
+//  HYPOTHESIS TESTS
+//  t-tests, chi-squared tests, one-way ANOVA and z-tests for
+//  proportions, with p-values and confidence intervals
+//  ------------------------------------------------------------
+
+// Alternative hypothesis, as MATLAB's 'Tail' option: the parameter differs from (Both),
+// is smaller than (Left) or is larger than (Right) its value under the null hypothesis
+#[wasm_bindgen]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum Tail {
+    Both,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct HypothesisTest {
+    pub statistic: f64,
+    // Degrees of freedom of the statistic (infinite for z-tests), numerator ones for F tests,
+    // NaN for the rank and Kolmogorov-Smirnov tests
+    pub df: f64,
+    // Denominator degrees of freedom, for F tests only
+    pub df2: Option<f64>,
+    pub p_value: f64,
+    // Confidence interval at level 1 - alpha, for tests on a location parameter
+    pub confidence_interval: Option<(f64, f64)>,
+}
+
+fn invalid_input(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+fn check_alpha(alpha: f64) -> Result<(), Error> {
+    if alpha > 0.0 && alpha < 1.0 {
+        Ok(())
+    } else {
+        Err(invalid_input(
+            "The significance level must be between 0 and 1",
+        ))
+    }
+}
+
+// Test of a location estimate whose standardized deviation from the null value follows
+// a t distribution with df degrees of freedom, or the standard normal one if df is infinite
+fn location_test(
+    estimate: f64,
+    null: f64,
+    standard_error: f64,
+    df: f64,
+    alpha: f64,
+    tail: Tail,
+) -> HypothesisTest {
+    let cdf = |x: f64| -> f64 {
+        if df.is_infinite() {
+            s_normcdf(x)
+        } else {
+            tcdf(x, df)
+        }
+    };
+    let inv = |p: f64| -> f64 {
+        if df.is_infinite() {
+            s_norminv(p)
+        } else {
+            tinv(p, df)
+        }
+    };
+
+    let statistic = (estimate - null) / standard_error;
+
+    let (p_value, confidence_interval) = match tail {
+        Tail::Both => {
+            let margin = inv(1.0 - 0.5 * alpha) * standard_error;
+            (
+                (2.0 * cdf(-statistic.abs())).min(1.0),
+                (estimate - margin, estimate + margin),
+            )
+        }
+        Tail::Left => (
+            cdf(statistic),
+            (
+                f64::NEG_INFINITY,
+                estimate + inv(1.0 - alpha) * standard_error,
+            ),
+        ),
+        Tail::Right => (
+            cdf(-statistic),
+            (estimate - inv(1.0 - alpha) * standard_error, f64::INFINITY),
+        ),
+    };
+
+    HypothesisTest {
+        statistic,
+        df,
+        df2: None,
+        p_value,
+        confidence_interval: Some(confidence_interval),
+    }
+}
+
+// Upper tail of the F distribution, without the cancellation of 1 - fcdf
+fn f_survival(x: f64, v1: f64, v2: f64) -> f64 {
+    regincbet(v2 / (v2 + v1 * x), 0.5 * v2, 0.5 * v1)
+}
+
+// One-sample t-test of the mean of x against mu
+pub fn ttest(x: &[f64], mu: f64, alpha: f64, tail: Tail) -> Result<HypothesisTest, Error> {
+    check_alpha(alpha)?;
+    if x.len() < 2 {
+        return Err(invalid_input("The t-test needs at least two observations"));
+    }
+
+    let moments = Moments::of(x);
+    let standard_error = (moments.variance(1.0) / moments.n).sqrt();
+    Ok(location_test(
+        moments.mean(),
+        mu,
+        standard_error,
+        moments.n - 1.0,
+        alpha,
+        tail,
+    ))
+}
+
+// Paired t-test of the mean of the differences x - y against 0
+pub fn ttest_paired(x: &[f64], y: &[f64], alpha: f64, tail: Tail) -> Result<HypothesisTest, Error> {
+    if x.len() != y.len() {
+        return Err(invalid_input("Paired samples must have the same length"));
+    }
+
+    let differences: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    ttest(&differences, 0.0, alpha, tail)
+}
+
+// Two-sample t-test of the difference of the means of x and y against 0: Student's test
+// with a pooled variance, or Welch's test with Satterthwaite's degrees of freedom
+pub fn ttest2(
+    x: &[f64],
+    y: &[f64],
+    alpha: f64,
+    tail: Tail,
+    equal_variances: bool,
+) -> Result<HypothesisTest, Error> {
+    check_alpha(alpha)?;
+    if x.len() < 2 || y.len() < 2 {
+        return Err(invalid_input(
+            "The t-test needs at least two observations per sample",
+        ));
+    }
+
+    let (mx, my) = (Moments::of(x), Moments::of(y));
+    let difference = mx.mean() - my.mean();
+
+    let (standard_error, v) = if equal_variances {
+        let v = mx.n + my.n - 2.0;
+        let pooled = (mx.m2 + my.m2) / v;
+        ((pooled * (mx.n.recip() + my.n.recip())).sqrt(), v)
+    } else {
+        let a = mx.variance(1.0) / mx.n;
+        let b = my.variance(1.0) / my.n;
+        let v = (a + b).powi(2) / (a * a / (mx.n - 1.0) + b * b / (my.n - 1.0));
+        ((a + b).sqrt(), v)
+    };
+
+    Ok(location_test(
+        difference,
+        0.0,
+        standard_error,
+        v,
+        alpha,
+        tail,
+    ))
+}
+
+// Chi-squared goodness-of-fit test of observed counts against expected counts or probabilities
+// (rescaled to the observed total, uniform if empty), with ddof parameters estimated from the data
+pub fn chi2gof(observed: &[f64], expected: &[f64], ddof: f64) -> Result<HypothesisTest, Error> {
+    let k = observed.len();
+    if k < 2 {
+        return Err(invalid_input("The test needs at least two categories"));
+    } else if !expected.is_empty() && expected.len() != k {
+        return Err(invalid_input("Mismatched observed and expected counts"));
+    } else if expected.iter().any(|&e| e.is_nan() || e <= 0.0) {
+        return Err(invalid_input("Expected counts must be positive"));
+    }
+
+    let total: f64 = observed.iter().sum();
+    let expected: Vec<f64> = if expected.is_empty() {
+        vec![total / k as f64; k]
+    } else {
+        let scale = total / expected.iter().sum::<f64>();
+        expected.iter().map(|e| e * scale).collect()
+    };
+
+    let statistic: f64 = observed
+        .iter()
+        .zip(&expected)
+        .map(|(o, e)| (o - e) * (o - e) / e)
+        .sum();
+    let df = k as f64 - 1.0 - ddof;
+    if df <= 0.0 {
+        return Err(invalid_input("No degrees of freedom left"));
+    }
+
+    Ok(HypothesisTest {
+        statistic,
+        df,
+        df2: None,
+        p_value: reguppincgamma(0.5 * df, 0.5 * statistic),
+        confidence_interval: None,
+    })
+}
+
+// Chi-squared test of independence of the rows and columns of a contingency table,
+// given row by row with the number of columns
+pub fn chi2_independence(table: &[f64], columns: usize) -> Result<HypothesisTest, Error> {
+    if columns < 2 || !table.len().is_multiple_of(columns) || table.len() / columns < 2 {
+        return Err(invalid_input("The contingency table must be at least 2x2"));
+    }
+
+    let rows = table.len() / columns;
+    let row_sums: Vec<f64> = table.chunks(columns).map(|row| row.iter().sum()).collect();
+    let column_sums: Vec<f64> = (0..columns)
+        .map(|j| table.iter().skip(j).step_by(columns).sum())
+        .collect();
+    let total: f64 = row_sums.iter().sum();
+
+    if row_sums.iter().chain(&column_sums).any(|&sum| sum <= 0.0) {
+        return Err(invalid_input("Every row and column needs a positive count"));
+    }
+
+    let mut statistic = 0.0;
+    for i in 0..rows {
+        for j in 0..columns {
+            let expected = row_sums[i] * column_sums[j] / total;
+            statistic += (table[i * columns + j] - expected).powi(2) / expected;
+        }
+    }
+
+    let df = ((rows - 1) * (columns - 1)) as f64;
+    Ok(HypothesisTest {
+        statistic,
+        df,
+        df2: None,
+        p_value: reguppincgamma(0.5 * df, 0.5 * statistic),
+        confidence_interval: None,
+    })
+}
+
+// One-way analysis of variance: F test of the equality of the means of the groups
+pub fn anova1(groups: &[Vec<f64>]) -> Result<HypothesisTest, Error> {
+    if groups.len() < 2 || groups.iter().any(|g| g.is_empty()) {
+        return Err(invalid_input("ANOVA needs at least two non-empty groups"));
+    }
+
+    let mut total = Moments::default();
+    let mut within = 0.0;
+    for group in groups {
+        let moments = Moments::of(group);
+        within += moments.m2;
+        total.merge(&moments);
+    }
+
+    let v1 = groups.len() as f64 - 1.0;
+    let v2 = total.n - groups.len() as f64;
+    if v2 <= 0.0 {
+        return Err(invalid_input("ANOVA needs more observations than groups"));
+    }
+
+    let between = total.m2 - within;
+    let statistic = (between / v1) / (within / v2);
+
+    Ok(HypothesisTest {
+        statistic,
+        df: v1,
+        df2: Some(v2),
+        p_value: f_survival(statistic, v1, v2),
+        confidence_interval: None,
+    })
+}
+
+// z-test of a proportion, with the Wald confidence interval
+pub fn prop_ztest(
+    successes: f64,
+    n: f64,
+    p0: f64,
+    alpha: f64,
+    tail: Tail,
+) -> Result<HypothesisTest, Error> {
+    check_alpha(alpha)?;
+    if !is_count(successes) || !is_count(n) || successes > n || n == 0.0 {
+        return Err(invalid_input(
+            "Successes must be a count between 0 and the number of trials",
+        ));
+    } else if !(p0 > 0.0 && p0 < 1.0) {
+        return Err(invalid_input("The null proportion must be between 0 and 1"));
+    }
+
+    let p = successes / n;
+    let mut test = location_test(
+        p,
+        p0,
+        (p0 * (1.0 - p0) / n).sqrt(),
+        f64::INFINITY,
+        alpha,
+        tail,
+    );
+
+    // The interval uses the estimated proportion, the statistic the null one
+    let estimated = location_test(
+        p,
+        p0,
+        (p * (1.0 - p) / n).sqrt(),
+        f64::INFINITY,
+        alpha,
+        tail,
+    );
+    test.confidence_interval = estimated.confidence_interval;
+    Ok(test)
+}
+
+// z-test of the difference of two proportions, pooled under the null hypothesis of equality
+pub fn prop_ztest2(
+    successes1: f64,
+    n1: f64,
+    successes2: f64,
+    n2: f64,
+    alpha: f64,
+    tail: Tail,
+) -> Result<HypothesisTest, Error> {
+    check_alpha(alpha)?;
+    for (successes, n) in [(successes1, n1), (successes2, n2)] {
+        if !is_count(successes) || !is_count(n) || successes > n || n == 0.0 {
+            return Err(invalid_input(
+                "Successes must be a count between 0 and the number of trials",
+            ));
+        }
+    }
+
+    let (p1, p2) = (successes1 / n1, successes2 / n2);
+    let pooled = (successes1 + successes2) / (n1 + n2);
+    let pooled_error = (pooled * (1.0 - pooled) * (n1.recip() + n2.recip())).sqrt();
+    let mut test = location_test(p1 - p2, 0.0, pooled_error, f64::INFINITY, alpha, tail);
+
+    let error = (p1 * (1.0 - p1) / n1 + p2 * (1.0 - p2) / n2).sqrt();
+    test.confidence_interval =
+        location_test(p1 - p2, 0.0, error, f64::INFINITY, alpha, tail).confidence_interval;
+    Ok(test)
+}
+
+fn js_test_output(test: Result<HypothesisTest, Error>) -> Result<String, JsValue> {
+    let test = test.map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_json::to_string(&test).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = ttest)]
+pub fn js_ttest(x: &[f64], mu: f64, alpha: f64, tail: Tail) -> Result<String, JsValue> {
+    js_test_output(ttest(x, mu, alpha, tail))
+}
+
+#[wasm_bindgen(js_name = ttest_paired)]
+pub fn js_ttest_paired(x: &[f64], y: &[f64], alpha: f64, tail: Tail) -> Result<String, JsValue> {
+    js_test_output(ttest_paired(x, y, alpha, tail))
+}
+
+#[wasm_bindgen(js_name = ttest2)]
+pub fn js_ttest2(
+    x: &[f64],
+    y: &[f64],
+    alpha: f64,
+    tail: Tail,
+    equal_variances: bool,
+) -> Result<String, JsValue> {
+    js_test_output(ttest2(x, y, alpha, tail, equal_variances))
+}
+
+#[wasm_bindgen(js_name = chi2gof)]
+pub fn js_chi2gof(observed: &[f64], expected: &[f64], ddof: f64) -> Result<String, JsValue> {
+    js_test_output(chi2gof(observed, expected, ddof))
+}
+
+#[wasm_bindgen(js_name = chi2_independence)]
+pub fn js_chi2_independence(table: &[f64], columns: usize) -> Result<String, JsValue> {
+    js_test_output(chi2_independence(table, columns))
+}
+
+// The groups are a JSON array of arrays of numbers
+#[wasm_bindgen(js_name = anova1)]
+pub fn js_anova1(groups: String) -> Result<String, JsValue> {
+    let groups: Vec<Vec<f64>> =
+        serde_json::from_str(&groups).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_test_output(anova1(&groups))
+}
+
+#[wasm_bindgen(js_name = prop_ztest)]
+pub fn js_prop_ztest(
+    successes: f64,
+    n: f64,
+    p0: f64,
+    alpha: f64,
+    tail: Tail,
+) -> Result<String, JsValue> {
+    js_test_output(prop_ztest(successes, n, p0, alpha, tail))
+}
+
+#[wasm_bindgen(js_name = prop_ztest2)]
+pub fn js_prop_ztest2(
+    successes1: f64,
+    n1: f64,
+    successes2: f64,
+    n2: f64,
+    alpha: f64,
+    tail: Tail,
+) -> Result<String, JsValue> {
+    js_test_output(prop_ztest2(successes1, n1, successes2, n2, alpha, tail))
+}
+
+//  ------------------------------------------------------------
+//  NONPARAMETRIC TESTS
+//  Rank tests, Kolmogorov-Smirnov tests and the Shapiro-Wilk
+//  test of normality
+//  ------------------------------------------------------------
+
+// Largest sample size for the exact null distributions of the rank statistics,
+// whose counts stay exactly representable
+const EXACT_RANK_SIZE: usize = 50;
+// Largest sample size for the exact Kolmogorov distribution
+const EXACT_KOLMOGOROV_SIZE: usize = 100;
+// Largest product of the sample sizes for the exact two-sample Kolmogorov-Smirnov distribution
+const EXACT_KS2_PRODUCT: usize = 10_000;
+
+// Ranks from 1, tied values sharing the average of their ranks,
+// and the sum of t^3 - t over the groups of t ties
+fn ranks(x: &[f64]) -> (Vec<f64>, f64) {
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_by(|&a, &b| x[a].total_cmp(&x[b]));
+
+    let mut ranks = vec![0.0; x.len()];
+    let mut ties = 0.0;
+    let mut i = 0;
+    while i < order.len() {
+        let count = order[i..]
+            .iter()
+            .take_while(|&&k| x[k] == x[order[i]])
+            .count();
+        let rank = i as f64 + 0.5 * (count as f64 + 1.0);
+        for &k in &order[i..i + count] {
+            ranks[k] = rank;
+        }
+
+        let t = count as f64;
+        ties += t * t * t - t;
+        i += count;
+    }
+
+    (ranks, ties)
+}
+
+// Null distribution of the Mann-Whitney U statistic for samples of sizes m and n, from the
+// number of m-subsets of the ranks 1..m+n with each rank sum
+fn mann_whitney_distribution(m: usize, n: usize) -> Vec<f64> {
+    let total = m + n;
+    let max_sum = m * (2 * total - m + 1) / 2;
+    let mut counts = vec![vec![0.0; max_sum + 1]; m + 1];
+    counts[0][0] = 1.0;
+
+    for rank in 1..=total {
+        for k in (1..=m.min(rank)).rev() {
+            for sum in (rank..=max_sum).rev() {
+                counts[k][sum] += counts[k - 1][sum - rank];
+            }
+        }
+    }
+
+    let offset = m * (m + 1) / 2;
+    let ways: f64 = counts[m].iter().sum();
+    counts[m][offset..].iter().map(|c| c / ways).collect()
+}
+
+// Null distribution of the Wilcoxon signed-rank statistic for n differences,
+// from the number of subsets of the ranks 1..n with each sum
+fn signed_rank_distribution(n: usize) -> Vec<f64> {
+    let max_sum = n * (n + 1) / 2;
+    let mut counts = vec![0.0; max_sum + 1];
+    counts[0] = 1.0;
+
+    for rank in 1..=n {
+        for sum in (rank..=max_sum).rev() {
+            counts[sum] += counts[sum - rank];
+        }
+    }
+
+    let ways = 2.0_f64.powi(n as i32);
+    counts.iter().map(|c| c / ways).collect()
+}
+
+// p-value of an integer statistic from its exact null distribution
+fn exact_p_value(pmf: &[f64], statistic: f64, tail: Tail) -> f64 {
+    let at = statistic.round() as usize;
+    let left: f64 = pmf[..=at.min(pmf.len() - 1)].iter().sum();
+    let right: f64 = pmf[at.min(pmf.len())..].iter().sum();
+
+    match tail {
+        Tail::Both => (2.0 * left.min(right)).min(1.0),
+        Tail::Left => left.min(1.0),
+        Tail::Right => right.min(1.0),
+    }
+}
+
+// p-value of a statistic from its normal approximation, with a continuity correction
+fn normal_p_value(statistic: f64, mean: f64, variance: f64, tail: Tail) -> f64 {
+    let deviation = statistic - mean;
+    let sd = variance.sqrt();
+
+    match tail {
+        Tail::Both => (2.0 * s_normcdf(-(deviation.abs() - 0.5).max(0.0) / sd)).min(1.0),
+        Tail::Left => s_normcdf((deviation + 0.5) / sd),
+        Tail::Right => s_normcdf(-(deviation - 0.5) / sd),
+    }
+}
+
+fn rank_test(statistic: f64, p_value: f64) -> HypothesisTest {
+    HypothesisTest {
+        statistic,
+        df: f64::NAN,
+        df2: None,
+        p_value,
+        confidence_interval: None,
+    }
+}
+
+// Mann-Whitney U test (Wilcoxon rank sum test) of a shift between the distributions of x and y,
+// Left meaning that x tends to be smaller. The statistic is the U of x, its p-value exact
+// for small samples without ties and from the normal approximation otherwise.
+pub fn ranksum(x: &[f64], y: &[f64], tail: Tail) -> Result<HypothesisTest, Error> {
+    if x.is_empty() || y.is_empty() || x.iter().chain(y).any(|v| v.is_nan()) {
+        return Err(invalid_input(
+            "Both samples must be non-empty, without NaNs",
+        ));
+    }
+
+    let pooled: Vec<f64> = x.iter().chain(y).copied().collect();
+    let (ranks, ties) = ranks(&pooled);
+    let (m, n) = (x.len(), y.len());
+    let (mf, nf) = (m as f64, n as f64);
+    let statistic = ranks[..m].iter().sum::<f64>() - mf * (mf + 1.0) / 2.0;
+
+    let p_value = if ties == 0.0 && m + n <= EXACT_RANK_SIZE {
+        exact_p_value(&mann_whitney_distribution(m, n), statistic, tail)
+    } else {
+        let total = mf + nf;
+        let variance = mf * nf / 12.0 * (total + 1.0 - ties / (total * (total - 1.0)));
+        normal_p_value(statistic, 0.5 * mf * nf, variance, tail)
+    };
+
+    Ok(rank_test(statistic, p_value))
+}
+
+// Wilcoxon signed-rank test of the median of x against m, zeros differences dropped.
+// The statistic is the sum of the ranks of the positive differences.
+pub fn signrank(x: &[f64], m: f64, tail: Tail) -> Result<HypothesisTest, Error> {
+    if x.iter().any(|v| v.is_nan()) || m.is_nan() {
+        return Err(invalid_input("The sample must not have NaNs"));
+    }
+
+    let differences: Vec<f64> = x.iter().map(|v| v - m).filter(|&d| d != 0.0).collect();
+    if differences.is_empty() {
+        return Err(invalid_input(
+            "The signed-rank test needs non-zero differences",
+        ));
+    }
+
+    let magnitudes: Vec<f64> = differences.iter().map(|d| d.abs()).collect();
+    let (ranks, ties) = ranks(&magnitudes);
+    let statistic: f64 = ranks
+        .iter()
+        .zip(&differences)
+        .filter(|(_, &d)| d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+
+    let n = differences.len();
+    let p_value = if ties == 0.0 && n <= EXACT_RANK_SIZE {
+        exact_p_value(&signed_rank_distribution(n), statistic, tail)
+    } else {
+        let nf = n as f64;
+        let variance = nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - ties / 48.0;
+        normal_p_value(statistic, nf * (nf + 1.0) / 4.0, variance, tail)
+    };
+
+    Ok(rank_test(statistic, p_value))
+}
+
+// Paired Wilcoxon signed-rank test of the median of the differences x - y against 0
+pub fn signrank_paired(x: &[f64], y: &[f64], tail: Tail) -> Result<HypothesisTest, Error> {
+    if x.len() != y.len() {
+        return Err(invalid_input("Paired samples must have the same length"));
+    }
+
+    let differences: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    signrank(&differences, 0.0, tail)
+}
+
+// Kruskal-Wallis test of the equality of the distributions of the groups,
+// with the chi-squared approximation of the tie-corrected statistic H
+pub fn kruskalwallis(groups: &[Vec<f64>]) -> Result<HypothesisTest, Error> {
+    if groups.len() < 2 || groups.iter().any(|g| g.is_empty()) {
+        return Err(invalid_input(
+            "The Kruskal-Wallis test needs at least two non-empty groups",
+        ));
+    } else if groups.iter().flatten().any(|v| v.is_nan()) {
+        return Err(invalid_input("The groups must not have NaNs"));
+    }
+
+    let pooled: Vec<f64> = groups.iter().flatten().copied().collect();
+    let (ranks, ties) = ranks(&pooled);
+    let n = pooled.len() as f64;
+
+    let mut start = 0;
+    let mut sum = 0.0;
+    for group in groups {
+        let rank_sum: f64 = ranks[start..start + group.len()].iter().sum();
+        sum += rank_sum * rank_sum / group.len() as f64;
+        start += group.len();
+    }
+
+    let correction = 1.0 - ties / (n * n * n - n);
+    if correction <= 0.0 {
+        return Err(invalid_input("All the values are equal"));
+    }
+
+    let statistic = (12.0 / (n * (n + 1.0)) * sum - 3.0 * (n + 1.0)) / correction;
+    let df = groups.len() as f64 - 1.0;
+
+    Ok(HypothesisTest {
+        statistic,
+        df,
+        df2: None,
+        p_value: reguppincgamma(0.5 * df, 0.5 * statistic),
+        confidence_interval: None,
+    })
+}
+
+// Survival function of the limiting Kolmogorov distribution, P(K > lambda)
+fn kolmogorov_survival(lambda: f64) -> f64 {
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+
+    if lambda < 1.18 {
+        // Jacobi theta form of the cumulate distribution, fast for small arguments
+        let mut cdf = 0.0;
+        for k in 1..=20 {
+            let j = (2 * k - 1) as f64;
+            cdf += (-j * j * PI * PI / (8.0 * lambda * lambda)).exp();
+        }
+        1.0 - (2.0 * PI).sqrt() / lambda * cdf
+    } else {
+        let mut survival = 0.0;
+        for k in 1..=20 {
+            let k = k as f64;
+            let sign = if k % 2.0 == 1.0 { 1.0 } else { -1.0 };
+            survival += sign * (-2.0 * k * k * lambda * lambda).exp();
+        }
+        (2.0 * survival).clamp(0.0, 1.0)
+    }
+}
+
+fn matrix_product(a: &[f64], b: &[f64], size: usize) -> Vec<f64> {
+    let mut product = vec![0.0; size * size];
+    for i in 0..size {
+        for k in 0..size {
+            let aik = a[i * size + k];
+            if aik != 0.0 {
+                for j in 0..size {
+                    product[i * size + j] += aik * b[k * size + j];
+                }
+            }
+        }
+    }
+    product
+}
+
+// n-th power of a matrix by squaring, as a matrix and a power of 10 that scales it,
+// keeping the entries in range
+fn matrix_power(matrix: &[f64], size: usize, n: usize) -> (Vec<f64>, i32) {
+    if n == 1 {
+        return (matrix.to_vec(), 0);
+    }
+
+    let (half, half_scale) = matrix_power(matrix, size, n / 2);
+    let mut power = matrix_product(&half, &half, size);
+    let mut scale = 2 * half_scale;
+    if n % 2 == 1 {
+        power = matrix_product(&power, matrix, size);
+    }
+
+    let center = (size / 2) * size + size / 2;
+    if power[center] > 1e140 {
+        power.iter_mut().for_each(|v| *v *= 1e-140);
+        scale += 140;
+    }
+
+    (power, scale)
+}
+
+// P(D_n < d) for the two-sided one-sample statistic, exact (Marsaglia, Tsang and Wang)
+fn kolmogorov_cdf(n: usize, d: f64) -> f64 {
+    let nf = n as f64;
+    if d <= 0.5 / nf {
+        return 0.0;
+    } else if d >= 1.0 {
+        return 1.0;
+    }
+
+    let k = (nf * d).floor() as usize + 1;
+    let size = 2 * k - 1;
+    let h = k as f64 - nf * d;
+
+    let mut matrix = vec![0.0; size * size];
+    for i in 0..size {
+        for j in 0..size {
+            if i + 1 >= j {
+                matrix[i * size + j] = 1.0;
+            }
+        }
+    }
+    for i in 0..size {
+        matrix[i * size] -= h.powi(i as i32 + 1);
+        matrix[(size - 1) * size + i] -= h.powi((size - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        matrix[(size - 1) * size] += (2.0 * h - 1.0).powi(size as i32);
+    }
+    for i in 0..size {
+        for j in 0..size {
+            if i + 1 > j {
+                for g in 1..=(i + 1 - j) {
+                    matrix[i * size + j] /= g as f64;
+                }
+            }
+        }
+    }
+
+    let (power, scale) = matrix_power(&matrix, size, n);
+
+    // n! / n^n in log space
+    let ln_factor = lgamma(nf + 1.0) - nf * nf.ln();
+    let corner = power[(k - 1) * size + k - 1];
+    (corner.ln() + ln_factor + scale as f64 * LN_10)
+        .exp()
+        .clamp(0.0, 1.0)
+}
+
+// P(D+_n >= d) for the one-sided one-sample statistic, exact (Birnbaum and Tingey)
+fn smirnov_survival(n: usize, d: f64) -> f64 {
+    if d <= 0.0 {
+        return 1.0;
+    } else if d >= 1.0 {
+        return 0.0;
+    }
+
+    let nf = n as f64;
+    let last = (nf * (1.0 - d)).floor() as usize;
+    let mut sum = 0.0;
+    for j in 0..=last {
+        let jf = j as f64;
+        // At a lattice d = k/n, the last term vanishes but rounds to the log of a negative number
+        let gap = 1.0 - d - jf / nf;
+        if gap <= 0.0 {
+            continue;
+        }
+        let ln_term = lnchoose(nf, jf) + (nf - jf) * gap.ln() + (jf - 1.0) * (d + jf / nf).ln();
+        sum += ln_term.exp();
+    }
+
+    (d * sum).clamp(0.0, 1.0)
+}
+
+// One-sample Kolmogorov-Smirnov test of x against a continuous cumulate distribution.
+// Like MATLAB's kstest, Right ('larger') tests whether the cdf of the data is larger than the
+// hypothesized one, with statistic D+ = max(F_n - F), and Left ('smaller') uses D- = max(F - F_n).
+pub fn kstest<F: Fn(f64) -> f64>(x: &[f64], cdf: F, tail: Tail) -> Result<HypothesisTest, Error> {
+    let sorted =
+        ordered(x).ok_or_else(|| invalid_input("The sample must be non-empty, without NaNs"))?;
+    let n = sorted.len();
+    let nf = n as f64;
+
+    let (mut above, mut below) = (0.0_f64, 0.0_f64);
+    for (i, &value) in sorted.iter().enumerate() {
+        let f = cdf(value);
+        if !(0.0..=1.0).contains(&f) {
+            return Err(invalid_input(
+                "The cumulate distribution must return numbers in [0, 1]",
+            ));
+        }
+        above = above.max((i + 1) as f64 / nf - f);
+        below = below.max(f - i as f64 / nf);
+    }
+
+    let (statistic, p_value) = match tail {
+        Tail::Both => {
+            let d = above.max(below);
+            let p = if n <= EXACT_KOLMOGOROV_SIZE {
+                1.0 - kolmogorov_cdf(n, d)
+            } else {
+                // Stephens' correction of the limiting distribution
+                kolmogorov_survival((nf.sqrt() + 0.12 + 0.11 / nf.sqrt()) * d)
+            };
+            (d, p)
+        }
+        Tail::Right => (above, smirnov_survival(n, above)),
+        Tail::Left => (below, smirnov_survival(n, below)),
+    };
+
+    Ok(rank_test(statistic, p_value))
+}
+
+// P(D < d) for two samples of sizes m and n, counting the lattice paths of the merged sample
+// that keep |i/m - j/n| (or i/m - j/n for one side) below d
+fn ks2_cdf(m: usize, n: usize, d: f64, two_sided: bool) -> f64 {
+    let (mf, nf) = (m as f64, n as f64);
+    // Distances equal to d, up to rounding, are not below it
+    let bound = d - 1e-12;
+    let inside = |i: usize, j: usize| -> bool {
+        let distance = i as f64 / mf - j as f64 / nf;
+        if two_sided {
+            distance.abs() < bound
+        } else {
+            distance < bound
+        }
+    };
+
+    // Probability of reaching (i, j) within the band, along a uniformly random path
+    let mut row = vec![0.0; n + 1];
+    for i in 0..=m {
+        for j in 0..=n {
+            row[j] = if i == 0 && j == 0 {
+                1.0
+            } else if !inside(i, j) {
+                0.0
+            } else {
+                let from_above = if i > 0 { row[j] * i as f64 } else { 0.0 };
+                let from_left = if j > 0 { row[j - 1] * j as f64 } else { 0.0 };
+                (from_above + from_left) / (i + j) as f64
+            };
+        }
+    }
+
+    row[n]
+}
+
+// Two-sample Kolmogorov-Smirnov test of the equality of the distributions of x and y.
+// Like MATLAB's kstest2, Right ('larger') tests whether the cdf of x is larger than the cdf of y,
+// with statistic max(F_x - F_y), and Left ('smaller') uses max(F_y - F_x).
+pub fn kstest2(x: &[f64], y: &[f64], tail: Tail) -> Result<HypothesisTest, Error> {
+    let sx =
+        ordered(x).ok_or_else(|| invalid_input("The samples must be non-empty, without NaNs"))?;
+    let sy =
+        ordered(y).ok_or_else(|| invalid_input("The samples must be non-empty, without NaNs"))?;
+    let (m, n) = (sx.len(), sy.len());
+
+    // Walk through the merged sample, comparing the cdfs after each distinct value
+    let (mut i, mut j) = (0, 0);
+    let (mut above, mut below) = (0.0_f64, 0.0_f64);
+    while i < m || j < n {
+        let value = if j == n || (i < m && sx[i] <= sy[j]) {
+            sx[i]
+        } else {
+            sy[j]
+        };
+        while i < m && sx[i] == value {
+            i += 1;
+        }
+        while j < n && sy[j] == value {
+            j += 1;
+        }
+
+        let difference = i as f64 / m as f64 - j as f64 / n as f64;
+        above = above.max(difference);
+        below = below.max(-difference);
+    }
+
+    let statistic = match tail {
+        Tail::Both => above.max(below),
+        Tail::Right => above,
+        Tail::Left => below,
+    };
+    let two_sided = tail == Tail::Both;
+
+    let p_value = if m * n <= EXACT_KS2_PRODUCT {
+        // One-sided: the band is reflected for the smaller side
+        let cdf = if tail == Tail::Left {
+            ks2_cdf(n, m, statistic, false)
+        } else {
+            ks2_cdf(m, n, statistic, two_sided)
+        };
+        (1.0 - cdf).clamp(0.0, 1.0)
+    } else {
+        let effective = (m * n) as f64 / (m + n) as f64;
+        if two_sided {
+            kolmogorov_survival((effective.sqrt() + 0.12 + 0.11 / effective.sqrt()) * statistic)
+        } else {
+            (-2.0 * effective * statistic * statistic).exp()
+        }
+    };
+
+    Ok(rank_test(statistic, p_value))
+}
+
+// Polynomial with coefficients in ascending powers
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+// Shapiro-Wilk test of normality, for 3 to 5000 values, with Royston's approximations
+// of the coefficients and of the p-value (algorithm AS R94)
+pub fn shapiro_wilk(x: &[f64]) -> Result<HypothesisTest, Error> {
+    let sorted =
+        ordered(x).ok_or_else(|| invalid_input("The sample must be non-empty, without NaNs"))?;
+    let n = sorted.len();
+    if !(3..=5000).contains(&n) {
+        return Err(invalid_input(
+            "The Shapiro-Wilk test needs between 3 and 5000 values",
+        ));
+    } else if sorted[0] == sorted[n - 1] {
+        return Err(invalid_input("All the values are equal"));
+    }
+
+    let nf = n as f64;
+
+    // Coefficients: normalized expected normal order statistics, corrected in the tails
+    let a: Vec<f64> = if n == 3 {
+        vec![-FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2]
+    } else {
+        let m: Vec<f64> = (1..=n)
+            .map(|i| s_norminv((i as f64 - 0.375) / (nf + 0.25)))
+            .collect();
+        let mm: f64 = m.iter().map(|v| v * v).sum();
+        let u = nf.sqrt().recip();
+
+        let an = m[n - 1] / mm.sqrt()
+            + polynomial(
+                &[0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056],
+                u,
+            );
+        let mut a: Vec<f64>;
+        if n > 5 {
+            let an1 = m[n - 2] / mm.sqrt()
+                + polynomial(
+                    &[0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633],
+                    u,
+                );
+            let phi = (mm - 2.0 * m[n - 1] * m[n - 1] - 2.0 * m[n - 2] * m[n - 2])
+                / (1.0 - 2.0 * an * an - 2.0 * an1 * an1);
+            a = m.iter().map(|v| v / phi.sqrt()).collect();
+            a[n - 2] = an1;
+            a[1] = -an1;
+        } else {
+            let phi = (mm - 2.0 * m[n - 1] * m[n - 1]) / (1.0 - 2.0 * an * an);
+            a = m.iter().map(|v| v / phi.sqrt()).collect();
+        }
+        a[n - 1] = an;
+        a[0] = -an;
+        a
+    };
+
+    let mean = mean(&sorted);
+    let numerator: f64 = a.iter().zip(&sorted).map(|(a, x)| a * x).sum();
+    let denominator: f64 = sorted.iter().map(|x| (x - mean) * (x - mean)).sum();
+    let statistic = (numerator * numerator / denominator).min(1.0);
+
+    let p_value = if n == 3 {
+        (6.0 / PI * (statistic.sqrt().asin() - FRAC_PI_3)).clamp(0.0, 1.0)
+    } else {
+        // Normalizing transformation of ln(1 - W)
+        let y = (-statistic).ln_1p();
+        let (z, mu, sigma) = if n <= 11 {
+            let gamma = -2.273 + 0.459 * nf;
+            if y >= gamma {
+                return Ok(rank_test(statistic, 0.0));
+            }
+            (
+                -(gamma - y).ln(),
+                polynomial(&[0.544, -0.39978, 0.025054, -6.714e-4], nf),
+                polynomial(&[1.3822, -0.77857, 0.062767, -0.0020322], nf).exp(),
+            )
+        } else {
+            let ln_n = nf.ln();
+            (
+                y,
+                polynomial(&[-1.5861, -0.31082, -0.083751, 0.0038915], ln_n),
+                polynomial(&[-0.4803, -0.082676, 0.0030302], ln_n).exp(),
+            )
+        };
+        s_normcdf(-(z - mu) / sigma)
+    };
+
+    Ok(rank_test(statistic, p_value))
+}
+
+#[wasm_bindgen(js_name = ranksum)]
+pub fn js_ranksum(x: &[f64], y: &[f64], tail: Tail) -> Result<String, JsValue> {
+    js_test_output(ranksum(x, y, tail))
+}
+
+#[wasm_bindgen(js_name = signrank)]
+pub fn js_signrank(x: &[f64], m: f64, tail: Tail) -> Result<String, JsValue> {
+    js_test_output(signrank(x, m, tail))
+}
+
+#[wasm_bindgen(js_name = signrank_paired)]
+pub fn js_signrank_paired(x: &[f64], y: &[f64], tail: Tail) -> Result<String, JsValue> {
+    js_test_output(signrank_paired(x, y, tail))
+}
+
+// The groups are a JSON array of arrays of numbers
+#[wasm_bindgen(js_name = kruskalwallis)]
+pub fn js_kruskalwallis(groups: String) -> Result<String, JsValue> {
+    let groups: Vec<Vec<f64>> =
+        serde_json::from_str(&groups).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_test_output(kruskalwallis(&groups))
+}
+
+// The cumulate distribution is a JS callback (x) => F(x)
+#[wasm_bindgen(js_name = kstest)]
+pub fn js_kstest(x: &[f64], cdf: &js_sys::Function, tail: Tail) -> Result<String, JsValue> {
+    let failure = RefCell::new(None);
+    let test = kstest(x, js_function(cdf, &failure), tail);
+
+    match failure.into_inner() {
+        Some(e) => Err(e),
+        None => js_test_output(test),
+    }
+}
+
+#[wasm_bindgen(js_name = kstest2)]
+pub fn js_kstest2(x: &[f64], y: &[f64], tail: Tail) -> Result<String, JsValue> {
+    js_test_output(kstest2(x, y, tail))
+}
+
+#[wasm_bindgen(js_name = shapiro_wilk)]
+pub fn js_shapiro_wilk(x: &[f64]) -> Result<String, JsValue> {
+    js_test_output(shapiro_wilk(x))
+}
+
+/**
+ * |----------------------------|
+ * |----------------------------|
+ * |         UNIT TESTS         |
+ * |----------------------------|
+ * |----------------------------|
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /**
+     *  ------------------------------
+     *  NORMAL DISTRIBUTION UNIT TESTS
+     *  ------------------------------
+     */
+
+    #[test]
+    fn normpdf_test() {
+        // Does the normal probability function compute the correct result?
+        assert_eq!(normpdf(1.0, 1.0, 1.0), 0.3989422804014327);
+
+        // Standardized with sigma, not only shifted by mu
+        assert!((normpdf(1.5, 1.0, 2.0) - 0.19333405840142465).abs() < 1e-16);
+    }
+
+    #[test]
+    fn s_normpdf_test() {
+        // Does the normal probability function compute the correct result?
+
+        //  Approximate the result
+        // (two different square root algorithms, so slightly different results are expected)
+        let s_norm: String = s_normpdf(1.0).to_string().chars().take(5).collect();
+        let norm: String = normpdf(1.0, 0.0, 1.0).to_string().chars().take(5).collect();
+
+        assert_eq!(s_norm, norm);
+    }
+
+    #[test]
+    fn normcdf_test() {
+        // Does the cumulative probability function compute the correct result?
+        assert!((normcdf(0.5, 1.0, 2.0) - 0.4012936743170763).abs() < 1e-15);
+    }
+
+    #[test]
+    fn s_normcdf_test() {
+        // Does the cumulative probability function compute the correct result?
+        assert_eq!(s_normcdf(0.0), 0.5);
+
+        // Symmetric, and accurate in both tails
+        assert!((s_normcdf(-10.0) / 7.619853024160526e-24 - 1.0).abs() < 1e-13);
+        assert!((s_normcdf(5.0) - 0.9999997133484281).abs() < 1e-15);
+        assert!((s_normcdf(-1.3) + s_normcdf(1.3) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn s_norminv_test() {
+        // Does the inverse probability function compute the correct result?
+        assert_eq!(s_norminv(0.5), 0.0);
+        assert!((s_norminv(0.3) + 0.5244005127080408).abs() < 1e-15);
+        assert!((s_norminv(0.975) - 1.959963984540054).abs() < 1e-15);
+        assert!((s_norminv(0.02425) + 1.972961051311885).abs() < 1e-15);
+        assert!((s_norminv(0.9999) - 3.71901648545568).abs() < 1e-13);
+        assert!((s_norminv(1e-10) + 6.361340902404056).abs() < 1e-14);
+        assert!((s_norminv(1e-300) + 37.0470962993612).abs() < 1e-13);
+        assert_eq!(s_norminv(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn norminv_test() {
+        // Does the inverse probability function compute the correct result?
+        assert!((norminv(0.3, 4.0, 2.0) - 2.951198974583918).abs() < 1e-14);
+    }
+
+    #[test]
+    fn erf_test() {
+        assert!((erf(0.5) - 0.5204998778130465).abs() < 1e-15);
+        assert!((erf(-1.5) + 0.9661051464753107).abs() < 1e-15);
+        assert_eq!(erf(1e-20), 1.1283791670955126e-20);
+        assert!((erfc(5.0) / 1.537459794428035e-12 - 1.0).abs() < 1e-14);
+        assert!((erfc(-1.0) - 1.842700792949715).abs() < 1e-15);
+        assert!((erfc(26.0) / 5.663192408856143e-296 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn erfinv_test() {
+        assert!((erfinv(0.5) - 0.4769362762044699).abs() < 1e-15);
+        assert!((erfinv(-0.999) + 2.326753765513525).abs() < 1e-14);
+        assert!((erfinv(1e-12) / 8.86226925452758e-13 - 1.0).abs() < 1e-15);
+        assert!((erfinv(1.0 - 2f64.powi(-50)) - 5.686128441310391).abs() < 1e-14);
+        assert_eq!(erfinv(-1.0), f64::NEG_INFINITY);
+        assert!(erfinv(1.5).is_nan());
+    }
+
+    #[test]
+    fn lognormcdf_test() {
+        assert_eq!(s_lognormcdf(0.0), -LN_2);
+        assert!((s_lognormcdf(-5.0) + 15.064998393988726).abs() < 1e-12);
+        assert!((s_lognormcdf(10.0) / -7.619853024160526e-24 - 1.0).abs() < 1e-13);
+
+        // Far beyond the underflow of normcdf
+        assert!((s_lognormcdf(-50.0) + 1254.8313611394199).abs() < 1e-10);
+        assert!((lognormcdf(-395.0, 5.0, 2.0) + 20006.21728089819).abs() < 1e-8);
+
+        assert!((lognormpdf(1.5, 1.0, 2.0) - normpdf(1.5, 1.0, 2.0).ln()).abs() < 1e-15);
+    }
+
+    /**
+     *  -----------------------------
+     *  GAMMA DISTRIBUTION UNIT TESTS
+     *  -----------------------------
+     */
+
+    #[test]
+    fn gamma_test() {
+        // Extract the result
+        let gamma = gamma(2.3);
+
+        // Round to 5 digits
+        let gamma = (gamma * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(gamma, 1.1667);
+    }
+
+    #[test]
+    fn gamma_accuracy_test() {
+        // Reflection formula for negative arguments
+        assert!((gamma(-1.5) - 2.363271801207355).abs() < 1e-14);
+        assert!((gamma(0.1) - 9.513507698668732).abs() < 1e-13);
+
+        // Poles and factorials
+        assert_eq!(gamma(0.0), f64::INFINITY);
+        assert_eq!(gamma(-3.0), f64::INFINITY);
+        assert_eq!(gamma(6.0), 120.0);
+
+        // No overflow before the true one
+        assert!((gamma(150.5) / 4.661_072_627_097_378e261 - 1.0).abs() < 1e-12);
+        assert_eq!(gamma(172.0), f64::INFINITY);
+        assert_eq!(gamma(f64::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn lgamma_test() {
+        // Compare with MATLAB gammaln
+        assert!((lgamma(100.0) - 359.1342053695754).abs() < 1e-12);
+        assert!((lgamma(0.5) - 0.5723649429247001).abs() < 1e-15);
+        assert_eq!(lgamma(1.0), 0.0);
+        assert_eq!(lgamma(2.0), 0.0);
+
+        // Logarithm of |gamma| for negative arguments
+        assert!((lgamma(-2.5) + 0.05624371649767405).abs() < 1e-14);
+
+        // Far beyond the overflow of gamma
+        assert!((lgamma(1e6) - 12815504.569147612).abs() < 1e-6);
+        assert_eq!(lgamma(f64::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn digamma_test() {
+        // Minus the Euler-Mascheroni constant
+        assert!((digamma(1.0) + 0.5772156649015329).abs() < 1e-14);
+        assert!((digamma(3.7) - 1.1671535393615114).abs() < 1e-15);
+        assert!((digamma(-0.5) - 0.03648997397857652).abs() < 1e-14);
+        assert!(digamma(-2.0).is_nan());
+    }
+
+    #[test]
+    fn trigamma_test() {
+        // pi^2 / 6
+        assert!((trigamma(1.0) - PI * PI / 6.0).abs() < 1e-15);
+        assert!((trigamma(0.3) - 12.245364546107731).abs() < 1e-13);
+        assert!((trigamma(-0.5) - 8.934802200544679).abs() < 1e-13);
+    }
+
+    #[test]
+    fn lowincgamma_test() {
+        // Extract the result
+        let lowincgamma = lowincgamma(3.5, 1.44);
+
+        // Round to 5 digits
         let lowincgamma = (lowincgamma * 10000.0).round() / 10000.0;
 
-        // Compare with MATLAB result
-        assert_eq!(lowincgamma, 0.1041);
+        // Compare with MATLAB result: gammainc(1.44, 3.5) * gamma(3.5)
+        assert_eq!(lowincgamma, 0.346);
+    }
+
+    #[test]
+    fn uppincgamma_test() {
+        // Extract the result
+        let uppincgamma = uppincgamma(3.5, 1.44);
+
+        // Round to 5 digits
+        let uppincgamma = (uppincgamma * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result: gammainc(1.44, 3.5, 'upper') * gamma(3.5)
+        assert_eq!(uppincgamma, 2.9773);
+    }
+
+    #[test]
+    fn regincgamma_test() {
+        // Compare with MATLAB result: gammainc(1.44, 3.5)
+        let p = (regincgamma(3.5, 1.44) * 10000.0).round() / 10000.0;
+        assert_eq!(p, 0.1041);
+        let q = (reguppincgamma(3.5, 1.44) * 10000.0).round() / 10000.0;
+        assert_eq!(q, 0.8959);
+
+        // Both expansions, and the extreme cases
+        assert!((regincgamma(0.1, 0.01) - 0.6626212599544798).abs() < 1e-14);
+        assert!((regincgamma(500.0, 480.0) - 0.1862819731903246).abs() < 1e-12);
+        assert!((reguppincgamma(2.5, 30.0) / 1.215456977718304e-11 - 1.0).abs() < 1e-12);
+        assert_eq!(regincgamma(2.0, 0.0), 0.0);
+        assert!(regincgamma(-1.0, 1.0).is_nan());
+
+        // Inverse
+        assert!((regincgammainv(0.001, 0.2) / 6.525480843457249e-16 - 1.0).abs() < 1e-10);
+        assert!((regincgammainv(0.999, 50.0) - 74.72462638951935).abs() < 1e-11);
+        assert_eq!(regincgammainv(1.0, 3.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn gammapdf_test() {
+        // Compute the result
+        let pdf = gammapdf(0.93, 3.0, 7.0);
+
+        // Round to 5 digits
+        let pdf = (pdf * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(pdf, 0.0011);
+    }
+
+    #[test]
+    fn gammacdf_test() {
+        // Compute the result
+        let cdf = gammacdf(1.23, 2.0, 2.0);
+
+        // Round to 5 digits
+        let cdf = (cdf * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(cdf, 0.1269);
+    }
+
+    #[test]
+    fn gammainv_test() {
+        // Compute the result
+        let inv = gammainv(0.68, 3.0, 3.0);
+
+        // Round to 5 digits
+        let inv = (inv * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(inv, 10.5138);
+
+        // Round trip
+        for &p in &[1e-10, 0.05, 0.5, 0.95, 1.0 - 1e-10] {
+            assert!((gammacdf(gammainv(p, 0.7, 2.0), 0.7, 2.0) - p).abs() < 1e-12);
+        }
+    }
+
+    /**
+     *  -----------------------
+     *  BETA DISTRIBUTION TESTS
+     *  -----------------------
+     */
+
+    #[test]
+    fn beta_test() {
+        // Extract the result
+        let beta = beta(3.0, 2.0);
+
+        // Round to 5 digits
+        let beta = (beta * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(beta, 0.0833);
+    }
+
+    #[test]
+    fn incbet_test() {
+        // Extract the result
+        let incbet = incbet(0.3, 2.0, 6.0);
+
+        // Round to 4 digits
+        let incbet = (incbet * 1000.0).round() / 1000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(incbet, 0.0160);
+    }
+
+    #[test]
+    fn regincbet_test() {
+        // Extract the result
+        let regincbet = regincbet(0.3, 11.0, 7.0);
+
+        // Round to 5 digits
+        let regincbet = (regincbet * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(regincbet, 0.0032);
+    }
+
+    #[test]
+    fn regincbet_accuracy_test() {
+        // Shape parameters below one, where the integrand is singular
+        assert!((regincbet(0.2, 0.5, 0.5) - 0.2951672353008666).abs() < 1e-14);
+        assert!((regincbet(0.001, 0.3, 2.5) - 0.176845639962568).abs() < 1e-14);
+
+        // Large shape parameters
+        assert!((regincbet(0.42, 200.0, 300.0) - 0.8196010664172663).abs() < 1e-12);
+        assert!((betaln(300.0, 400.0) + 479.688451037132).abs() < 1e-10);
+
+        // Inverse
+        assert!((regincbetinv(0.01, 0.3, 0.7) / 3.584084499488803e-7 - 1.0).abs() < 1e-10);
+        assert!((regincbetinv(0.999, 50.0, 2.0) - 0.9991013094706363).abs() < 1e-14);
+        for &p in &[1e-8, 0.1, 0.5, 0.9, 1.0 - 1e-8] {
+            assert!((betacdf(betainv(p, 2.5, 0.8), 2.5, 0.8) - p).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn betapdf_test() {
+        // Compute the result
+        let pdf = betapdf(0.01, 2.0, 5.0);
+
+        // Round to 5 digits
+        let pdf = (pdf * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(pdf, 0.2882);
+    }
+
+    #[test]
+    fn betacdf_test() {
+        // Compute the result
+        let cdf = betacdf(0.6, 9.0, 4.0);
+
+        // Round to 5 digits
+        let cdf = (cdf * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(cdf, 0.2253);
+    }
+
+    #[test]
+    fn betainv_test() {
+        // Compute the result
+        let inv = betainv(0.72, 3.0, 4.0);
+
+        // Round to 5 digits
+        let inv = (inv * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(inv, 0.5354);
+    }
+
+    //  --------------------
+    //  T DISTRIBUTION TESTS
+    //  --------------------
+
+    #[test]
+    fn tpdf_test() {
+        // Extract the result
+        let tpdf = tpdf(0.8, 11.0);
+
+        // Round to 5 digits
+        let tpdf = (tpdf * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(tpdf, 0.2778);
+    }
+
+    #[test]
+    fn tcdf_test() {
+        // Extract the first result
+        let tcdf_1 = tcdf(3.2, 11.0);
+
+        // Round to 5 digits
+        let tcdf_1 = (tcdf_1 * 10000.0).round() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(tcdf_1, 0.9958);
+
+        // Extract second the result
+        let tcdf_2 = tcdf(-3.2, 11.0);
+        let tcdf_2 = (tcdf_2 * 10000.0).round() / 10000.0;
+        assert_eq!(tcdf_2, 0.0042);
+
+        // Third result
+        let tcdf_3 = tcdf(0.0, 11.0);
+        let tcdf_3 = (tcdf_3 * 10000.0).round() / 10000.0;
+        assert_eq!(tcdf_3, 0.5);
+    }
+
+    #[test]
+    fn tinv_test() {
+        // Extract the result
+        let tinv = tinv(0.4, 8.0);
+
+        // Truncate to 5 digits
+        let tinv = (tinv * 10000.0).trunc() / 10000.0;
+
+        // Compare with MATLAB result
+        assert_eq!(tinv, -0.2619);
+    }
+
+    // ------------------------
+    // CHI^2 DISTRIBUTION TESTS
+    // ------------------------
+
+    #[test]
+    fn chi2pdf_test() {
+        // Compute the result
+        let pdf = chi2pdf(0.56, 5.0);
+
+        // Round to 5 digits
+        let pdf = (pdf * 10000.0).round() / 10000.0;
+
+        // Check against MATLAB result
+        assert_eq!(pdf, 0.0421);
+    }
+
+    #[test]
+    fn chi2cdf_test() {
+        // Compute the result
+        let cdf = chi2cdf(0.42, 3.0);
+
+        // Round to 5 digits
+        let cdf = (cdf * 10000.0).round() / 10000.0;
+
+        // Check against MATLAB result
+        assert_eq!(cdf, 0.0639);
+    }
+
+    #[test]
+    fn chi2inv_test() {
+        // Compute the result
+        let inv = chi2inv(0.076, 4.0);
+
+        // Round to 5 digits
+        let inv = (inv * 10000.0).round() / 10000.0;
+
+        // Check against MATLAB result
+        assert_eq!(inv, 0.9039);
+    }
+
+    #[test]
+    fn chi2_accuracy_test() {
+        // Critical values
+        assert!((chi2inv(0.95, 1.0) - 3.841458820694124).abs() < 1e-12);
+        assert!((chi2inv(0.99, 1000.0) - 1106.968994352217).abs() < 1e-9);
+        assert!((chi2inv(0.001, 0.5) / 1.349939578623075e-12 - 1.0).abs() < 1e-10);
+        assert!((chi2cdf(3.841458820694124, 1.0) - 0.95).abs() < 1e-14);
+        assert_eq!(chi2cdf(0.0, 3.0), 0.0);
+    }
+
+    #[test]
+    fn ncx2_test() {
+        // Reference values of ncx2pdf(3, 4, 2) and ncx2cdf(3, 4, 2)
+        assert!((ncx2pdf(3.0, 4.0, 2.0) - 0.12083649092711131).abs() < 1e-14);
+        assert!((ncx2cdf(3.0, 4.0, 2.0) - 0.24627270146198138).abs() < 1e-14);
+
+        // Large noncentrality
+        assert!((ncx2cdf(300.0, 10.0, 250.0) - 0.891983270813511).abs() < 1e-12);
+        assert!((ncx2pdf(300.0, 10.0, 250.0) - 0.005400399676437046).abs() < 1e-14);
+
+        // No noncentrality: the central distribution
+        assert_eq!(ncx2cdf(2.5, 3.0, 0.0), chi2cdf(2.5, 3.0));
+
+        // Sankaran's approximation takes over from the Poisson mixture
+        let x = 2_002_000.0;
+        let (z, dz) = ncx2_sankaran(x, 4.0, 2e6);
+        assert!((ncx2cdf(x, 4.0, 2e6) - s_normcdf(z)).abs() < 1e-9);
+        assert!((ncx2pdf(x, 4.0, 2e6) - s_normpdf(z) * dz).abs() < 1e-13);
+        assert!((ncx2cdf(1e17 + 4.0, 4.0, 1e17) - 0.5).abs() < 1e-6);
+        assert_eq!(ncx2cdf(3.0, 4.0, 1e17), 0.0);
+        assert_eq!(ncx2pdf(3.0, 4.0, 1e17), 0.0);
+
+        // Infinite and invalid noncentralities
+        assert_eq!(ncx2cdf(3.0, 4.0, f64::INFINITY), 0.0);
+        assert_eq!(ncx2pdf(3.0, 4.0, f64::INFINITY), 0.0);
+        assert!(ncx2cdf(3.0, 4.0, f64::NAN).is_nan());
+        assert!(ncx2pdf(3.0, 4.0, -1.0).is_nan());
+        assert!(poisson_mixture(f64::INFINITY, |_| 1.0).is_nan());
+        assert!(poisson_mixture(f64::NAN, |_| 1.0).is_nan());
+        assert!(poisson_mixture(2f64.powi(53), |_| 1.0).is_nan());
+    }
+
+    //  ------------------------
+    //  DISTRIBUTION OBJECT TESTS
+    //  ------------------------
+
+    // The quantile inverts the cumulate distribution, whose derivative is the density.
+    // For a discrete distribution, the density is the jump of the cumulate distribution
+    // at x, and every probability within the jump has x as quantile
+    fn check_functions<P: Fn(f64) -> f64, C: Fn(f64) -> f64, Q: Fn(f64) -> f64>(
+        pdf: P,
+        cdf: C,
+        inv: Q,
+        points: &[f64],
+        discrete: bool,
+    ) {
+        for &x in points {
+            assert!((inv(cdf(x)) - x).abs() < 1e-9 * f64::max(x.abs(), 1.0));
+
+            if discrete {
+                assert!((cdf(x) - cdf(x - 1.0) - pdf(x)).abs() < 1e-12);
+                assert_eq!(inv(0.5 * (cdf(x - 1.0) + cdf(x))), x);
+            } else {
+                let h = 1e-5 * f64::max(x.abs(), 1.0);
+                let derivative = (cdf(x + h) - cdf(x - h)) / (2.0 * h);
+                assert!((derivative - pdf(x)).abs() < 1e-7);
+            }
+        }
+        assert!(inv(-0.1).is_nan());
+        assert!(inv(1.1).is_nan());
+    }
+
+    // Checks the consistency of a distribution object: on top of check_functions,
+    // sf is the complement of cdf and logpdf the logarithm of pdf
+    fn check_distribution<D: ContinuousDistribution>(d: &D, points: &[f64]) {
+        check_functions(|x| d.pdf(x), |x| d.cdf(x), |y| d.quantile(y), points, false);
+        for &x in points {
+            assert!((d.cdf(x) + d.sf(x) - 1.0).abs() < 1e-14);
+            assert!((d.logpdf(x) - d.pdf(x).ln()).abs() < 1e-12);
+        }
+
+        // Moments of the samples
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 100_000;
+        let samples: Vec<f64> = (0..n).map(|_| d.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        assert!((mean - d.mean()).abs() < 5.0 * (d.variance() / n as f64).sqrt());
+        assert!((variance / d.variance() - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn normal_distribution_test() {
+        let d = Normal::new(1.0, 2.0);
+        check_distribution(&d, &[-3.0, 0.0, 1.0, 2.5, 6.0]);
+
+        assert_eq!(d.mean(), 1.0);
+        assert_eq!(d.variance(), 4.0);
+        assert!((d.entropy() - 2.112085713764618).abs() < 1e-15);
+
+        // The survival function keeps its precision in the right tail
+        assert!((d.sf(21.0) / 7.619853024160526e-24 - 1.0).abs() < 1e-13);
+    }
+
+    #[test]
+    fn gamma_distribution_test() {
+        let d = Gamma::new(2.5, 1.5);
+        check_distribution(&d, &[0.1, 1.0, 3.75, 8.0]);
+
+        assert_eq!(d.mean(), 3.75);
+        assert_eq!(d.variance(), 5.625);
+        assert!((d.entropy() - 2.135413017613219).abs() < 1e-14);
+        assert!((d.sf(30.0) / 1.4933679000503952e-7 - 1.0).abs() < 1e-12);
+        assert_eq!(d.pdf(-1.0), 0.0);
+    }
+
+    #[test]
+    fn beta_distribution_test() {
+        let d = Beta::new(2.0, 5.0);
+        check_distribution(&d, &[0.05, 0.2, 0.5, 0.8]);
+
+        assert!((d.mean() - 2.0 / 7.0).abs() < 1e-16);
+        assert!((d.entropy() + 0.4845307149954887).abs() < 1e-14);
+        assert!((d.sf(0.9) / 5.499999999999994e-5 - 1.0).abs() < 1e-12);
+        assert_eq!(d.pdf(1.5), 0.0);
+    }
+
+    #[test]
+    fn student_t_distribution_test() {
+        let d = StudentT::new(5.0);
+        check_distribution(&d, &[-4.0, -0.5, 0.0, 1.2, 3.0]);
+
+        assert_eq!(d.variance(), 5.0 / 3.0);
+        assert_eq!(d.kurtosis(), 6.0);
+        assert!((d.entropy() - 1.627502672414396).abs() < 1e-14);
+
+        // Moments that don't exist
+        assert!(StudentT::new(1.0).mean().is_nan());
+        assert_eq!(StudentT::new(2.0).variance(), f64::INFINITY);
+
+        // Large degrees of freedom don't overflow
+        assert!((StudentT::new(1e6).pdf(1.3) - 0.1713685267606549).abs() < 1e-9);
+    }
+
+    #[test]
+    fn chi_squared_distribution_test() {
+        let d = ChiSquared::new(4.0);
+        check_distribution(&d, &[0.5, 2.0, 4.0, 10.0]);
+
+        assert_eq!(d.mean(), 4.0);
+        assert_eq!(d.variance(), 8.0);
+        assert!((d.entropy() - 2.270362845461478).abs() < 1e-14);
+    }
+
+    //  ---------------------
+    //  RANDOM VARIATES TESTS
+    //  ---------------------
+
+    // Largest distance between the empirical cdf of the samples and the cdf
+    // (Kolmogorov-Smirnov statistic)
+    fn cdf_distance<D: ContinuousDistribution>(d: &D, samples: &[f64]) -> f64 {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len() as f64;
+
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                let f = d.cdf(x);
+                f64::max((f - i as f64 / n).abs(), ((i + 1) as f64 / n - f).abs())
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn random_variates_test() {
+        // Reproducible from the seed
+        assert_eq!(normrnd(0.0, 1.0, 10, 3), normrnd(0.0, 1.0, 10, 3));
+        assert_ne!(normrnd(0.0, 1.0, 10, 3), normrnd(0.0, 1.0, 10, 4));
+
+        // With 20000 samples, the 0.1% critical value of the KS statistic is about 0.0138
+        let n = 20000;
+        assert!(cdf_distance(&Normal::new(2.0, 3.0), &normrnd(2.0, 3.0, n, 1)) < 0.0138);
+        assert!(cdf_distance(&Gamma::new(3.5, 2.0), &gamrnd(3.5, 2.0, n, 1)) < 0.0138);
+        assert!(cdf_distance(&Gamma::new(0.3, 1.0), &gamrnd(0.3, 1.0, n, 1)) < 0.0138);
+        assert!(cdf_distance(&Beta::new(0.5, 2.0), &betarnd(0.5, 2.0, n, 1)) < 0.0138);
+        assert!(cdf_distance(&StudentT::new(3.0), &trnd(3.0, n, 1)) < 0.0138);
+        assert!(cdf_distance(&ChiSquared::new(7.0), &chi2rnd(7.0, n, 1)) < 0.0138);
+
+        // Invalid parameters
+        assert!(gamrnd(-1.0, 1.0, 1, 1)[0].is_nan());
     }
 
+    //  ----------------------------
+    //  DISCRETE DISTRIBUTIONS TESTS
+    //  ----------------------------
+
     #[test]
-    fn uppincgamma_test() {
-        // Extract the result
-        let lowincgamma = uppincgamma(1.44, 3.5);
+    fn binomial_test() {
+        // Compare with MATLAB results
+        assert!((binopdf(3.0, 10.0, 0.3) - 0.266827932).abs() < 1e-15);
+        assert!((binocdf(3.0, 10.0, 0.3) - 0.6496107184).abs() < 1e-15);
+        assert_eq!(binoinv(0.5, 10.0, 0.3), 3.0);
+        assert!((binocdf(400.0, 1000.0, 0.4) - 0.5137298582871434).abs() < 1e-12);
 
-        // Round to 5 digits
-        let lowincgamma = (lowincgamma * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| binopdf(x, 12.0, 0.35),
+            |x| binocdf(x, 12.0, 0.35),
+            |y| binoinv(y, 12.0, 0.35),
+            &[0.0, 1.0, 4.0, 7.0, 11.0],
+            true,
+        );
 
-        // Compare with MATLAB result
-        assert_eq!(lowincgamma, 0.8959);
+        assert_eq!(binopdf(2.5, 10.0, 0.3), 0.0);
+        assert_eq!(binopdf(10.0, 10.0, 1.0), 1.0);
+        assert_eq!(binoinv(1.0, 10.0, 0.3), 10.0);
+        assert!(binopdf(1.0, 2.5, 0.3).is_nan());
     }
 
     #[test]
-    fn gammapdf_test() {
-        // Compute the result
-        let pdf = gammapdf(0.93, 3.0, 7.0);
+    fn poisson_test() {
+        assert!((poisspdf(2.0, 3.5) - 0.1849589734617008).abs() < 1e-15);
+        assert!((poisscdf(2.0, 3.5) - 0.3208471988621341).abs() < 1e-15);
+        assert_eq!(poissinv(0.95, 3.5), 7.0);
+        assert!((poisscdf(1000.0, 1000.0) - 0.508409367168506).abs() < 1e-12);
+        assert_eq!(poissinv(1.0, 3.5), f64::INFINITY);
 
-        // Round to 5 digits
-        let pdf = (pdf * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| poisspdf(x, 4.2),
+            |x| poisscdf(x, 4.2),
+            |y| poissinv(y, 4.2),
+            &[0.0, 2.0, 4.0, 9.0, 20.0],
+            true,
+        );
+    }
 
-        // Compare with MATLAB result
-        assert_eq!(pdf, 0.0011);
+    #[test]
+    fn geometric_test() {
+        assert!((geopdf(2.0, 0.2) - 0.128).abs() < 1e-16);
+        assert!((geocdf(2.0, 0.2) - 0.488).abs() < 1e-15);
+        assert_eq!(geoinv(0.5, 0.2), 3.0);
+
+        check_functions(
+            |x| geopdf(x, 0.3),
+            |x| geocdf(x, 0.3),
+            |y| geoinv(y, 0.3),
+            &[0.0, 1.0, 5.0, 12.0, 25.0],
+            true,
+        );
     }
 
     #[test]
-    fn gammacdf_test() {
-        // Compute the result
-        let cdf = gammacdf(1.23, 2.0, 2.0);
+    fn negative_binomial_test() {
+        assert!((nbinpdf(3.0, 2.0, 0.4) - 0.13824).abs() < 1e-15);
+        assert!((nbincdf(3.0, 2.0, 0.4) - 0.66304).abs() < 1e-15);
 
-        // Round to 5 digits
-        let cdf = (cdf * 10000.0).round() / 10000.0;
+        // Non-integer number of successes
+        check_functions(
+            |x| nbinpdf(x, 2.5, 0.3),
+            |x| nbincdf(x, 2.5, 0.3),
+            |y| nbininv(y, 2.5, 0.3),
+            &[0.0, 3.0, 6.0, 14.0, 30.0],
+            true,
+        );
+    }
 
-        // Compare with MATLAB result
-        assert_eq!(cdf, 0.1269);
+    #[test]
+    fn hypergeometric_test() {
+        assert!((hygepdf(1.0, 20.0, 5.0, 4.0) - 0.4695562435500516).abs() < 1e-13);
+        assert!((hygecdf(1.0, 20.0, 5.0, 4.0) - 0.7512899896800826).abs() < 1e-13);
+
+        check_functions(
+            |x| hygepdf(x, 50.0, 20.0, 15.0),
+            |x| hygecdf(x, 50.0, 20.0, 15.0),
+            |y| hygeinv(y, 50.0, 20.0, 15.0),
+            &[0.0, 2.0, 6.0, 10.0, 15.0],
+            true,
+        );
+
+        // Support starting above zero: 8 draws from 10 items with 6 successes
+        assert_eq!(hygepdf(3.0, 10.0, 6.0, 8.0), 0.0);
+        assert_eq!(hygeinv(0.0, 10.0, 6.0, 8.0), 4.0);
+        assert!(hygepdf(1.0, 10.0, 12.0, 3.0).is_nan());
     }
 
     #[test]
-    fn gammainv_test() {
-        // Compute the result
-        let inv = gammainv(0.68, 3.0, 3.0);
+    fn discrete_random_test() {
+        let mean = |v: Vec<f64>| -> f64 { v.iter().sum::<f64>() / v.len() as f64 };
+        let n = 20000;
 
-        // Round to 5 digits
-        let inv = (inv * 10000.0).round() / 10000.0;
+        // Within 5 standard errors of the expected value
+        assert!((mean(binornd(20.0, 0.3, n, 5)) - 6.0).abs() < 5.0 * (4.2 / n as f64).sqrt());
+        assert!((mean(poissrnd(7.5, n, 5)) - 7.5).abs() < 5.0 * (7.5 / n as f64).sqrt());
+        assert!((mean(geornd(0.25, n, 5)) - 3.0).abs() < 5.0 * (12.0 / n as f64).sqrt());
+        assert!((mean(nbinrnd(3.0, 0.5, n, 5)) - 3.0).abs() < 5.0 * (6.0 / n as f64).sqrt());
+        assert!((mean(hygernd(50.0, 20.0, 15.0, n, 5)) - 6.0).abs() < 0.1);
 
-        // Compare with MATLAB result
-        assert_eq!(inv, 10.5138);
+        assert_eq!(poissrnd(7.5, 5, 9), poissrnd(7.5, 5, 9));
     }
 
-    /**
-     *  -----------------------
-     *  BETA DISTRIBUTION TESTS
-     *  -----------------------
-     */
+    //  --------------------------------------
+    //  OTHER CONTINUOUS DISTRIBUTIONS TESTS
+    //  --------------------------------------
 
     #[test]
-    fn beta_test() {
-        // Extract the result
-        let beta = beta(3.0, 2.0);
+    fn f_distribution_test() {
+        // Compare with MATLAB results
+        assert!((fpdf(2.0, 3.0, 10.0) - 0.14821094155911785).abs() < 1e-14);
+        assert!((fcdf(2.0, 3.0, 10.0) - 0.8219925926248246).abs() < 1e-14);
+        assert!((finv(0.95, 3.0, 10.0) - 3.7082648190468444).abs() < 1e-12);
 
-        // Round to 5 digits
-        let beta = (beta * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| fpdf(x, 3.0, 10.0),
+            |x| fcdf(x, 3.0, 10.0),
+            |y| finv(y, 3.0, 10.0),
+            &[0.1, 0.5, 1.0, 2.0, 7.5],
+            false,
+        );
+        check_functions(
+            |x| fpdf(x, 20.0, 4.5),
+            |x| fcdf(x, 20.0, 4.5),
+            |y| finv(y, 20.0, 4.5),
+            &[0.3, 1.0, 4.0, 12.0],
+            false,
+        );
 
-        // Compare with MATLAB result
-        assert_eq!(beta, 0.0833);
+        assert_eq!(fpdf(0.0, 2.0, 5.0), 1.0);
+        assert_eq!(fpdf(-1.0, 2.0, 5.0), 0.0);
+        assert_eq!(finv(1.0, 2.0, 5.0), f64::INFINITY);
+        assert!(fcdf(1.0, -2.0, 5.0).is_nan());
     }
 
     #[test]
-    fn incbet_test() {
-        // Extract the result
-        let incbet = incbet(0.3, 2.0, 6.0);
+    fn exponential_test() {
+        assert!((exppdf(2.0, 4.0) - 0.25 * (-0.5_f64).exp()).abs() < 1e-16);
+        assert!((expcdf(2.0, 4.0) - (1.0 - (-0.5_f64).exp())).abs() < 1e-16);
+        assert_eq!(expinv(1.0, 4.0), f64::INFINITY);
 
-        // Round to 4 digits
-        let incbet = (incbet * 1000.0).round() / 1000.0;
+        // No cancellation for small arguments
+        assert!((expcdf(1e-20, 1.0) / 1e-20 - 1.0).abs() < 1e-15);
 
-        // Compare with MATLAB result
-        assert_eq!(incbet, 0.0160);
+        check_functions(
+            |x| exppdf(x, 4.0),
+            |x| expcdf(x, 4.0),
+            |y| expinv(y, 4.0),
+            &[0.01, 1.0, 4.0, 30.0],
+            false,
+        );
     }
 
     #[test]
-    fn regincbet_test() {
-        // Extract the result
-        let regincbet = regincbet(0.3, 11.0, 7.0);
+    fn lognormal_test() {
+        assert!((lognpdf(2.0, 0.5, 1.2) - 0.16408664214188606).abs() < 1e-15);
+        assert!((logncdf(2.0, 0.5, 1.2) - 0.5639359658118673).abs() < 1e-15);
+        assert_eq!(logninv(0.5, 0.5, 1.2), 0.5_f64.exp());
+        assert_eq!(lognpdf(-1.0, 0.5, 1.2), 0.0);
 
-        // Round to 5 digits
-        let regincbet = (regincbet * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| lognpdf(x, 0.5, 1.2),
+            |x| logncdf(x, 0.5, 1.2),
+            |y| logninv(y, 0.5, 1.2),
+            &[0.1, 1.0, 2.0, 20.0],
+            false,
+        );
+    }
 
-        // Compare with MATLAB result
-        assert_eq!(regincbet, 0.0032);
+    #[test]
+    fn weibull_test() {
+        assert!((wblpdf(3.0, 2.0, 1.5) - 0.1463042640445423).abs() < 1e-15);
+        assert!((wblcdf(3.0, 2.0, 1.5) - 0.8407240915099786).abs() < 1e-15);
+
+        // Shape 1 is the exponential distribution
+        assert!((wblpdf(3.0, 2.0, 1.0) - exppdf(3.0, 2.0)).abs() < 1e-16);
+
+        check_functions(
+            |x| wblpdf(x, 2.0, 1.5),
+            |x| wblcdf(x, 2.0, 1.5),
+            |y| wblinv(y, 2.0, 1.5),
+            &[0.2, 1.0, 3.0, 6.0],
+            false,
+        );
+        check_functions(
+            |x| wblpdf(x, 1.0, 0.7),
+            |x| wblcdf(x, 1.0, 0.7),
+            |y| wblinv(y, 1.0, 0.7),
+            &[0.5, 2.0, 10.0],
+            false,
+        );
     }
 
     #[test]
-    fn betapdf_test() {
-        // Compute the result
-        let pdf = betapdf(0.01, 2.0, 5.0);
+    fn cauchy_test() {
+        assert_eq!(cauchypdf(1.0, 1.0, 2.0), 0.5 / PI);
+        assert_eq!(cauchycdf(3.0, 1.0, 2.0), 0.75);
+        assert_eq!(cauchycdf(-1.0, 1.0, 2.0), 0.25);
+        assert!((cauchyinv(0.75, 1.0, 2.0) - 3.0).abs() < 1e-15);
 
-        // Round to 5 digits
-        let pdf = (pdf * 10000.0).round() / 10000.0;
+        // Far left tail: 1 / (pi |z|)
+        assert!((cauchycdf(-1e12, 0.0, 1.0) * PI * 1e12 - 1.0).abs() < 1e-15);
 
-        // Compare with MATLAB result
-        assert_eq!(pdf, 0.2882);
+        check_functions(
+            |x| cauchypdf(x, 1.0, 2.0),
+            |x| cauchycdf(x, 1.0, 2.0),
+            |y| cauchyinv(y, 1.0, 2.0),
+            &[-50.0, -1.0, 1.0, 4.0, 100.0],
+            false,
+        );
     }
 
     #[test]
-    fn betacdf_test() {
-        // Compute the result
-        let cdf = betacdf(0.6, 9.0, 4.0);
+    fn laplace_test() {
+        assert_eq!(laplacepdf(1.0, 1.0, 2.0), 0.25);
+        assert_eq!(laplacecdf(1.0, 1.0, 2.0), 0.5);
+        assert!((laplacecdf(3.0, 1.0, 2.0) - (1.0 - 0.5 * (-1.0_f64).exp())).abs() < 1e-16);
 
-        // Round to 5 digits
-        let cdf = (cdf * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| laplacepdf(x, 1.0, 2.0),
+            |x| laplacecdf(x, 1.0, 2.0),
+            |y| laplaceinv(y, 1.0, 2.0),
+            &[-10.0, -1.0, 0.5, 3.0, 12.0],
+            false,
+        );
+    }
 
-        // Compare with MATLAB result
-        assert_eq!(cdf, 0.2253);
+    #[test]
+    fn logistic_test() {
+        assert_eq!(logisticpdf(1.0, 1.0, 2.0), 0.125);
+        assert!((logisticcdf(3.0, 1.0, 2.0) - 0.7310585786300049).abs() < 1e-16);
+        assert_eq!(logisticinv(0.5, 1.0, 2.0), 1.0);
+        assert!(logisticpdf(1e4, 0.0, 1.0) >= 0.0);
+
+        check_functions(
+            |x| logisticpdf(x, 1.0, 2.0),
+            |x| logisticcdf(x, 1.0, 2.0),
+            |y| logisticinv(y, 1.0, 2.0),
+            &[-10.0, -1.0, 0.5, 3.0, 12.0],
+            false,
+        );
     }
 
     #[test]
-    fn betainv_test() {
-        // Compute the result
-        let inv = betainv(0.72, 3.0, 4.0);
+    fn pareto_test() {
+        assert_eq!(paretopdf(2.0, 1.0, 3.0), 0.1875);
+        assert_eq!(paretocdf(2.0, 1.0, 3.0), 0.875);
+        assert_eq!(paretopdf(0.5, 1.0, 3.0), 0.0);
+        assert_eq!(paretoinv(0.0, 1.0, 3.0), 1.0);
 
-        // Round to 5 digits
-        let inv = (inv * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| paretopdf(x, 1.0, 3.0),
+            |x| paretocdf(x, 1.0, 3.0),
+            |y| paretoinv(y, 1.0, 3.0),
+            &[1.5, 2.0, 10.0],
+            false,
+        );
+    }
 
-        // Compare with MATLAB result
-        assert_eq!(inv, 0.5354);
+    #[test]
+    fn uniform_test() {
+        assert_eq!(unifpdf(0.5, -1.0, 3.0), 0.25);
+        assert_eq!(unifpdf(4.0, -1.0, 3.0), 0.0);
+        assert_eq!(unifcdf(0.0, -1.0, 3.0), 0.25);
+        assert_eq!(unifcdf(5.0, -1.0, 3.0), 1.0);
+        assert_eq!(unifinv(0.75, -1.0, 3.0), 2.0);
+        assert!(unifpdf(0.5, 3.0, 1.0).is_nan());
+
+        check_functions(
+            |x| unifpdf(x, -1.0, 3.0),
+            |x| unifcdf(x, -1.0, 3.0),
+            |y| unifinv(y, -1.0, 3.0),
+            &[-0.5, 0.0, 2.5],
+            false,
+        );
     }
 
-    //  --------------------
-    //  T DISTRIBUTION TESTS
-    //  --------------------
+    //  ------------------------------------
+    //  NONCENTRAL DISTRIBUTIONS TESTS
+    //  ------------------------------------
 
     #[test]
-    fn tpdf_test() {
-        // Extract the result
-        let tpdf = tpdf(0.8, 11.0);
+    fn noncentral_t_test() {
+        // Compare with MATLAB results
+        assert!((nctcdf(2.0, 10.0, 1.5) - 0.6591540724421908).abs() < 1e-14);
+        assert!((nctcdf(-1.0, 10.0, 1.5) - 0.0077790953543366214).abs() < 1e-14);
+        assert!((nctcdf(1.0, 5.0, -0.5) - 0.9175559089432764).abs() < 1e-14);
+        assert!((nctpdf(2.0, 10.0, 1.5) - 0.31460591845019614).abs() < 1e-12);
+        assert!((nctpdf(0.0, 10.0, 1.5) - 0.1263249969243921).abs() < 1e-15);
 
-        // Round to 5 digits
-        let tpdf = (tpdf * 10000.0).round() / 10000.0;
+        // No noncentrality is the central t distribution
+        assert!((nctcdf(1.3, 7.0, 0.0) - tcdf(1.3, 7.0)).abs() < 1e-15);
+        assert!((nctcdf(-1.3, 7.0, 0.0) - tcdf(-1.3, 7.0)).abs() < 1e-15);
 
-        // Compare with MATLAB result
-        assert_eq!(tpdf, 0.2778);
+        check_functions(
+            |x| nctpdf(x, 10.0, 1.5),
+            |x| nctcdf(x, 10.0, 1.5),
+            |y| nctinv(y, 10.0, 1.5),
+            &[-1.0, 0.5, 1.5, 3.0, 6.0],
+            false,
+        );
+        check_functions(
+            |x| nctpdf(x, 4.0, -2.0),
+            |x| nctcdf(x, 4.0, -2.0),
+            |y| nctinv(y, 4.0, -2.0),
+            &[-6.0, -2.0, -0.5, 1.0],
+            false,
+        );
+
+        // Noncentralities beyond the Poisson mixtures
+        assert!((nctcdf(2100.0, 10.0, 2000.0) - 0.5254456275522263).abs() < 1e-8);
+        assert!((nctcdf(1800.0, 30.0, 2000.0) - 0.17606958526547534).abs() < 1e-8);
+        assert!((nctcdf(-1800.0, 30.0, -2000.0) - 0.8239304147345247).abs() < 1e-8);
+        assert_eq!(nctcdf(1.0, 10.0, 1e17), 0.0);
+        assert!((nctcdf(-1e5, 5.0, -2000.0) / 9.50855835127967e-9 - 1.0).abs() < 1e-8);
+
+        // The tails keep their precision and reach 0 and 1. Probabilities this close
+        // to 1 are only spelled to a few digits, and so are their quantiles
+        assert_eq!(nctcdf(1e300, 5.0, 3.0), 1.0);
+        assert_eq!(nctcdf(-1e300, 5.0, 3.0), 0.0);
+        assert!((nctcdf(-1000.0, 5.0, -3.0) / 1.659180502764821e-12 - 1.0).abs() < 1e-12);
+        assert!((nctinv(1.0 - 1e-15, 5.0, 3.0) / 4406.065324676732 - 1.0).abs() < 0.01);
+
+        // Infinite noncentralities push all the mass to one side
+        assert_eq!(nctcdf(1.0, 10.0, f64::INFINITY), 0.0);
+        assert_eq!(nctcdf(1.0, 10.0, f64::NEG_INFINITY), 1.0);
+        assert_eq!(nctpdf(1.0, 10.0, f64::INFINITY), 0.0);
+        assert_eq!(nctinv(0.5, 10.0, f64::INFINITY), f64::INFINITY);
+        assert_eq!(nctinv(0.5, 10.0, f64::NEG_INFINITY), f64::NEG_INFINITY);
     }
 
     #[test]
-    fn tcdf_test() {
-        // Extract the first result
-        let tcdf_1 = tcdf(3.2, 11.0);
+    fn noncentral_f_test() {
+        assert!((ncfcdf(2.0, 3.0, 10.0, 4.0) - 0.4663642160480801).abs() < 1e-14);
+        assert!((ncfpdf(2.0, 3.0, 10.0, 4.0) - 0.2271330226248523).abs() < 1e-14);
+        assert!((ncfcdf(2.0, 3.0, 10.0, 0.0) - fcdf(2.0, 3.0, 10.0)).abs() < 1e-15);
 
-        // Round to 5 digits
-        let tcdf_1 = (tcdf_1 * 10000.0).round() / 10000.0;
+        check_functions(
+            |x| ncfpdf(x, 3.0, 10.0, 4.0),
+            |x| ncfcdf(x, 3.0, 10.0, 4.0),
+            |y| ncfinv(y, 3.0, 10.0, 4.0),
+            &[0.2, 1.0, 2.0, 6.0],
+            false,
+        );
+        assert!(ncfcdf(2.0, 3.0, 10.0, -1.0).is_nan());
 
-        // Compare with MATLAB result
-        assert_eq!(tcdf_1, 0.9958);
+        // The mixture reaches 1, so that quantiles close to 1 are bracketed
+        assert_eq!(ncfcdf(f64::INFINITY, 5.0, 10.0, 1000.0), 1.0);
+        assert!((ncfinv(1.0 - 1e-14, 5.0, 10.0, 1000.0) / 245198.72150394018 - 1.0).abs() < 0.01);
 
-        // Extract second the result
-        let tcdf_2 = tcdf(-3.2, 11.0);
-        let tcdf_2 = (tcdf_2 * 10000.0).round() / 10000.0;
-        assert_eq!(tcdf_2, 0.0042);
+        // Patnaik's approximation takes over from the Poisson mixture
+        let (lambda, x) = (1.99e6, 663_333.0);
+        let (scale, v) = ncf_patnaik(3.0, lambda);
+        assert!((ncfcdf(x, 3.0, 20.0, lambda) - fcdf(x * scale, v, 20.0)).abs() < 1e-8);
+        assert_eq!(ncfcdf(2.0, 3.0, 10.0, 1e17), 0.0);
 
-        // Third result
-        let tcdf_3 = tcdf(0.0, 11.0);
-        let tcdf_3 = (tcdf_3 * 10000.0).round() / 10000.0;
-        assert_eq!(tcdf_3, 0.5);
+        assert_eq!(ncfcdf(2.0, 3.0, 10.0, f64::INFINITY), 0.0);
+        assert_eq!(ncfpdf(2.0, 3.0, 10.0, f64::INFINITY), 0.0);
+        assert_eq!(ncfinv(0.5, 3.0, 10.0, f64::INFINITY), f64::INFINITY);
     }
 
     #[test]
-    fn tinv_test() {
-        // Extract the result
-        let tinv = tinv(0.4, 8.0);
+    fn power_test() {
+        // Compare with G*Power results
+        assert!((ttest_power(0.5, 34.0, 0.05, true) - 0.8077775012792737).abs() < 1e-12);
+        assert!((ttest_power(0.5, 27.0, 0.05, false) - 0.8118315517081682).abs() < 1e-12);
+        assert!((ttest2_power(0.5, 64.0, 0.05, true) - 0.8014595579222542).abs() < 1e-12);
+        assert!((anova_power(0.25, 3.0, 53.0, 0.05) - 0.8048872853010696).abs() < 1e-12);
 
-        // Truncate to 5 digits
-        let tinv = (tinv * 10000.0).trunc() / 10000.0;
+        assert_eq!(ttest_samplesize(0.5, 0.8, 0.05, true), 34.0);
+        assert_eq!(ttest_samplesize(-0.5, 0.8, 0.05, false), 27.0);
+        assert_eq!(ttest2_samplesize(0.5, 0.8, 0.05, true), 64.0);
+        assert_eq!(anova_samplesize(0.25, 3.0, 0.8, 0.05), 53.0);
 
-        // Compare with MATLAB result
-        assert_eq!(tinv, -0.2619);
+        // No effect: the test rejects at rate alpha whatever the sample size
+        assert!((ttest_power(0.0, 20.0, 0.05, true) - 0.05).abs() < 1e-12);
+        assert_eq!(ttest2_samplesize(0.0, 0.8, 0.05, true), f64::INFINITY);
+        assert!(ttest_samplesize(0.5, 0.01, 0.05, true).is_nan());
+
+        // Overwhelming effects always reject
+        assert_eq!(ttest_power(f64::INFINITY, 10.0, 0.05, true), 1.0);
+        assert_eq!(ttest_power(1e4, 100.0, 0.05, false), 1.0);
+        assert_eq!(ttest2_power(1e17, 10.0, 0.05, true), 1.0);
+        assert_eq!(anova_power(f64::INFINITY, 3.0, 10.0, 0.05), 1.0);
+        assert_eq!(anova_power(1e4, 3.0, 10.0, 0.05), 1.0);
+
+        // Invalid effect sizes and group counts
+        assert!(ttest_samplesize(f64::NAN, 0.8, 0.05, true).is_nan());
+        assert!(ttest2_samplesize(f64::INFINITY, 0.8, 0.05, true).is_nan());
+        assert!(anova_samplesize(0.25, 2.5, 0.8, 0.05).is_nan());
+        assert!(anova_samplesize(0.25, 1.0, 0.8, 0.05).is_nan());
+        assert!(anova_samplesize(f64::NAN, 3.0, 0.8, 0.05).is_nan());
     }
 
-    // ------------------------
-    // CHI^2 DISTRIBUTION TESTS
-    // ------------------------
+    //  -------------------------------------
+    //  MULTIVARIATE NORMAL DISTRIBUTION TESTS
+    //  -------------------------------------
+
+    fn covariance(values: &[f64]) -> SquareMatrix {
+        let n = (values.len() as f64).sqrt() as usize;
+        let mut matrix = SquareMatrix::new(n);
+        for (i, &value) in values.iter().enumerate() {
+            matrix.set(i / n + 1, i % n + 1, value);
+        }
+        matrix
+    }
 
     #[test]
-    fn chi2pdf_test() {
-        // Compute the result
-        let pdf = chi2pdf(0.56, 5.0);
+    fn bvncdf_test() {
+        assert!((bvncdf(0.5, -0.3, 0.6) - 0.3436225301112108).abs() < 1e-12);
+        assert!((bvncdf(-1.0, 2.0, -0.9) - 0.13615368101504624).abs() < 1e-12);
+        assert!((bvncdf(1.5, 1.5, 0.99) - 0.9258931875038703).abs() < 1e-12);
 
-        // Round to 5 digits
-        let pdf = (pdf * 10000.0).round() / 10000.0;
+        // Orthant probability 1/4 + asin(rho) / (2 pi)
+        assert!((bvncdf(0.0, 0.0, 0.5) - 1.0 / 3.0).abs() < 1e-14);
+        assert!((bvncdf(0.7, -0.2, 0.0) - s_normcdf(0.7) * s_normcdf(-0.2)).abs() < 1e-15);
+        assert_eq!(bvncdf(0.7, f64::INFINITY, 0.3), s_normcdf(0.7));
+        assert!(bvncdf(0.0, 0.0, 1.5).is_nan());
+    }
 
-        // Check against MATLAB result
-        assert_eq!(pdf, 0.0421);
+    #[test]
+    fn multivariate_normal_test() {
+        let sigma = covariance(&[2.0, 0.6, -0.4, 0.6, 1.0, 0.3, -0.4, 0.3, 1.5]);
+        let d = MultivariateNormal::new(vec![0.5, -0.2, 1.0], sigma.clone()).unwrap();
+
+        assert!((d.logpdf(&[1.0, 0.5, 1.2]) - -3.344442980248516).abs() < 1e-14);
+        assert!((d.cdf(&[1.0, 0.5, 1.2]) - 0.295067213345758).abs() < 1e-4);
+        assert_eq!(d.probability(&[1.0, 0.0, 0.0], &[0.0, 1.0, 1.0]), 0.0);
+        assert!(d.logpdf(&[1.0, 0.5]).is_nan());
+
+        // Equicorrelated orthant probabilities: 1 / (n + 1) for rho = 1/2
+        for n in 3..=5 {
+            let values: Vec<f64> = (0..n * n)
+                .map(|i| if i % (n + 1) == 0 { 1.0 } else { 0.5 })
+                .collect();
+            let d = MultivariateNormal::new(vec![0.0; n], covariance(&values)).unwrap();
+            assert!((d.cdf(&vec![0.0; n]) - 1.0 / (n + 1) as f64).abs() < 1e-4);
+        }
+
+        // Two dimensions: inclusion-exclusion over the bivariate cumulate distribution
+        let d =
+            MultivariateNormal::new(vec![1.0, -1.0], covariance(&[4.0, 1.2, 1.2, 1.0])).unwrap();
+        let expected = bvncdf(0.25, 0.5, 0.6) - bvncdf(-0.5, 0.5, 0.6);
+        assert!((d.probability(&[0.0, f64::NEG_INFINITY], &[1.5, -0.5]) - expected).abs() < 1e-15);
+
+        // Non positive definite covariance
+        assert!(
+            MultivariateNormal::new(vec![0.0, 0.0], covariance(&[1.0, 2.0, 2.0, 1.0])).is_err()
+        );
+        assert!(MultivariateNormal::new(vec![0.0], sigma).is_err());
+
+        // No dimensions, and a covariance from JS with missing entries
+        assert!(MultivariateNormal::new(vec![], SquareMatrix::new(0)).is_err());
+        let truncated: SquareMatrix =
+            serde_json::from_str(r#"{"Size":2,"Matrix":[1,0,0]}"#).unwrap();
+        assert!(MultivariateNormal::new(vec![0.0, 0.0], truncated).is_err());
     }
 
     #[test]
-    fn chi2cdf_test() {
-        // Compute the result
-        let cdf = chi2cdf(0.42, 3.0);
+    fn multivariate_normal_sample_test() {
+        let d =
+            MultivariateNormal::new(vec![1.0, -2.0], covariance(&[2.0, -0.8, -0.8, 1.0])).unwrap();
+        let n = 50_000;
+        let samples = d.js_sample(n, 3);
+        assert_eq!(samples.len(), 2 * n);
 
-        // Round to 5 digits
-        let cdf = (cdf * 10000.0).round() / 10000.0;
+        let x: Vec<f64> = samples.iter().step_by(2).copied().collect();
+        let y: Vec<f64> = samples.iter().skip(1).step_by(2).copied().collect();
+        let mean = |v: &[f64]| -> f64 { v.iter().sum::<f64>() / v.len() as f64 };
+        let (mx, my) = (mean(&x), mean(&y));
+        let cov = x
+            .iter()
+            .zip(&y)
+            .map(|(a, b)| (a - mx) * (b - my))
+            .sum::<f64>()
+            / (n - 1) as f64;
 
-        // Check against MATLAB result
-        assert_eq!(cdf, 0.0639);
+        assert!((mx - 1.0).abs() < 5.0 * (2.0 / n as f64).sqrt());
+        assert!((my + 2.0).abs() < 5.0 * (1.0 / n as f64).sqrt());
+        assert!((cov + 0.8).abs() < 0.03);
     }
 
+    //  ------------------------------
+    //  DESCRIPTIVE STATISTICS TESTS
+    //  ------------------------------
+
     #[test]
-    fn chi2inv_test() {
-        // Compute the result
-        let inv = chi2inv(0.076, 4.0);
+    fn moments_test() {
+        let x = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
 
-        // Round to 5 digits
-        let inv = (inv * 10000.0).round() / 10000.0;
+        assert_eq!(mean(&x), 4.125);
+        assert!((variance(&x, 1.0) - 15.839285714285714).abs() < 1e-14);
+        assert!((variance(&x, 0.0) - 15.839285714285714 * 7.0 / 8.0).abs() < 1e-14);
+        assert!((stdev(&x, 1.0) - 15.839285714285714_f64.sqrt()).abs() < 1e-15);
+        assert!((skewness(&x) - 0.2650554122698573).abs() < 1e-14);
+        assert!((kurtosis(&x) - -1.6660010752838508).abs() < 1e-14);
 
-        // Check against MATLAB result
-        assert_eq!(inv, 0.9039);
+        // Welford's algorithm keeps its precision with a large offset
+        let shifted = [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0];
+        assert_eq!(variance(&shifted, 1.0), 30.0);
+
+        assert!(mean(&[]).is_nan());
+        assert!(variance(&[1.0], 1.0).is_nan());
+        assert_eq!(variance(&[1.0], 0.0), 0.0);
+    }
+
+    #[test]
+    fn order_statistics_test() {
+        let x = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+
+        assert_eq!(median(&x), 3.0);
+        assert_eq!(median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(mode(&x), 0.0);
+        assert_eq!(mode(&[1.0, 3.0, 3.0, 2.0]), 3.0);
+        assert_eq!(mad(&x), 3.0);
+        assert_eq!(min(&x), 0.0);
+        assert_eq!(max(&x), 9.0);
+        assert!(median(&[1.0, f64::NAN]).is_nan());
+
+        let summary = five_number_summary(&x).unwrap();
+        assert_eq!(summary.first_quartile, 0.75);
+        assert_eq!(summary.third_quartile, 8.25);
+        assert_eq!(iqr(&x), 7.5);
+        assert_eq!(summarize(&[]), "null");
+        assert_eq!(
+            summarize(&[1.0, 2.0, 3.0]),
+            "{\"Minimum\":1.0,\"FirstQuartile\":1.5,\"Median\":2.0,\"ThirdQuartile\":2.5,\"Maximum\":3.0}"
+        );
+    }
+
+    #[test]
+    fn quantile_test() {
+        // Compare with R's quantile(1:10, 0.25, type = t)
+        let x: Vec<f64> = (1..=10).rev().map(|i| i as f64).collect();
+        let expected = [
+            3.0,
+            3.0,
+            2.0,
+            2.5,
+            3.0,
+            2.75,
+            3.25,
+            2.9166666666666665,
+            2.9375,
+        ];
+        for (method, value) in (1..=9).zip(expected) {
+            assert!((quantile(&x, 0.25, method) - value).abs() < 1e-15);
+        }
+
+        assert_eq!(quantile(&x, 0.5, 1), 5.0);
+        assert_eq!(quantile(&x, 0.5, 2), 5.5);
+        assert_eq!(quantile(&x, 0.3, 1), 3.0);
+        assert_eq!(quantile(&x, 0.0, 7), 1.0);
+        assert_eq!(quantile(&x, 1.0, 6), 10.0);
+        assert_eq!(percentile(&x, 90.0, 7), 9.1);
+        assert!(quantile(&x, 0.5, 10).is_nan());
+        assert!(quantile(&x, 1.5, 7).is_nan());
+    }
+
+    //  ------------------------------
+    //  STREAMING STATISTICS TESTS
+    //  ------------------------------
+
+    // 0, 1, ..., n - 1 in a scrambled order
+    fn scrambled(n: usize) -> Vec<f64> {
+        (0..n).map(|i| ((i * 7919) % n) as f64).collect()
+    }
+
+    #[test]
+    fn accumulator_test() {
+        let x = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+        let mut accumulator = Accumulator::new();
+        accumulator.extend(&x);
+        accumulator.push(f64::NAN);
+
+        assert_eq!(accumulator.count(), 8.0);
+        assert_eq!(accumulator.mean(), mean(&x));
+        assert!((accumulator.variance(1.0) - variance(&x, 1.0)).abs() < 1e-14);
+        assert!((accumulator.skewness() - skewness(&x)).abs() < 1e-14);
+        assert!((accumulator.kurtosis() - kurtosis(&x)).abs() < 1e-14);
+        assert_eq!(accumulator.min(), 0.0);
+        assert_eq!(accumulator.max(), 9.0);
+        assert_eq!(accumulator.quantile(0.0), 0.0);
+        assert_eq!(accumulator.quantile(1.0), 9.0);
+
+        let empty = Accumulator::new();
+        assert!(empty.mean().is_nan());
+        assert!(empty.min().is_nan());
+    }
+
+    #[test]
+    fn accumulator_quantile_test() {
+        let n = 100_000;
+        let mut accumulator = Accumulator::new();
+        accumulator.extend(&scrambled(n));
+
+        // Relative to the range of the data, the error is smallest in the tails
+        for (p, tolerance) in [
+            (0.5, 0.005),
+            (0.1, 0.005),
+            (0.9, 0.005),
+            (0.99, 0.001),
+            (0.001, 0.0003),
+        ] {
+            let exact = p * (n - 1) as f64;
+            assert!((accumulator.quantile(p) - exact).abs() < tolerance * n as f64);
+        }
+        assert!(accumulator.digest.centroids.len() < 200);
+    }
+
+    #[test]
+    fn accumulator_merge_test() {
+        let n = 30_000;
+        let x: Vec<f64> = scrambled(n).iter().map(|v| (v / 1000.0).powi(2)).collect();
+
+        let mut whole = Accumulator::new();
+        whole.extend(&x);
+
+        // Uneven chunks, one of them empty, one of them sent around as JSON
+        let mut merged = Accumulator::new();
+        for chunk in [&x[..1000], &x[1000..1000], &x[1000..17000], &x[17000..]] {
+            let mut part = Accumulator::new();
+            part.extend(chunk);
+            let part = Accumulator::from_json(part.to_json()).unwrap();
+            merged.merge(&part);
+        }
+
+        assert_eq!(merged.count(), whole.count());
+        assert!((merged.mean() / whole.mean() - 1.0).abs() < 1e-13);
+        assert!((merged.variance(1.0) / whole.variance(1.0) - 1.0).abs() < 1e-12);
+        assert!((merged.skewness() / whole.skewness() - 1.0).abs() < 1e-11);
+        assert!((merged.kurtosis() / whole.kurtosis() - 1.0).abs() < 1e-11);
+        assert_eq!(merged.min(), whole.min());
+        assert_eq!(merged.max(), whole.max());
+
+        // Quantiles of the squares of uniform values
+        for p in [0.05, 0.25, 0.5, 0.75, 0.95] {
+            let exact = (p * 30.0_f64).powi(2);
+            assert!((merged.quantile(p) - exact).abs() < 0.01 * 900.0);
+        }
+    }
+
+    //  ------------------------------
+    //  HYPOTHESIS TESTS
+    //  ------------------------------
+
+    const X: [f64; 8] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.5, 5.3, 6.2];
+    const Y: [f64; 7] = [4.8, 5.0, 5.2, 5.1, 5.6, 4.9, 5.0];
+
+    #[test]
+    fn ttest_test() {
+        // Compare with MATLAB's ttest results
+        let test = ttest(&X, 5.0, 0.05, Tail::Both).unwrap();
+        assert!((test.statistic - 3.5036213149883335).abs() < 1e-12);
+        assert_eq!(test.df, 7.0);
+        assert!((test.p_value - 0.00994441433575158).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 5.178800255092534).abs() < 1e-12);
+        assert!((upper - 5.921199744907466).abs() < 1e-12);
+
+        let test = ttest(&X, 5.0, 0.05, Tail::Right).unwrap();
+        assert!((test.statistic - 3.5036213149883335).abs() < 1e-12);
+        assert_eq!(test.df, 7.0);
+        assert!((test.p_value - 0.00497220716787579).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 5.252588225405012).abs() < 1e-12);
+        assert_eq!(upper, f64::INFINITY);
+
+        let z = [5.0, 4.7, 5.5, 5.4, 5.9, 5.1, 5.4, 5.8];
+        let test = ttest_paired(&X, &z, 0.05, Tail::Both).unwrap();
+        assert!((test.statistic - 3.0550504633038935).abs() < 1e-12);
+        assert_eq!(test.df, 7.0);
+        assert!((test.p_value - 0.01845152851301588).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 0.04519900538496802).abs() < 1e-12);
+        assert!((upper - 0.354800994615032).abs() < 1e-12);
+
+        assert!(ttest(&[1.0], 0.0, 0.05, Tail::Both).is_err());
+        assert!(ttest(&X, 5.0, 1.5, Tail::Both).is_err());
+        assert!(ttest_paired(&X, &Y, 0.05, Tail::Both).is_err());
+    }
+
+    #[test]
+    fn ttest2_test() {
+        // Compare with MATLAB's ttest2 results, with 'Vartype' 'equal' and 'unequal'
+        let test = ttest2(&X, &Y, 0.05, Tail::Both, true).unwrap();
+        assert!((test.statistic - 2.4185342201895557).abs() < 1e-12);
+        assert_eq!(test.df, 13.0);
+        assert!((test.p_value - 0.030990177427716155).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 0.04956001124906976).abs() < 1e-12);
+        assert!((upper - 0.8790114173223588).abs() < 1e-12);
+
+        let test = ttest2(&X, &Y, 0.05, Tail::Both, false).unwrap();
+        assert!((test.statistic - 2.504325797327263).abs() < 1e-12);
+        assert!((test.df - 11.522551275553145).abs() < 1e-12);
+        assert!((test.p_value - 0.028414390695147885).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 0.05848380298951788).abs() < 1e-12);
+        assert!((upper - 0.8700876255819107).abs() < 1e-12);
+
+        let test = ttest2(&X, &Y, 0.05, Tail::Left, false).unwrap();
+        assert!((test.p_value - 0.985792804652426).abs() < 1e-12);
+        assert_eq!(test.confidence_interval.unwrap().0, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn chi2_tests_test() {
+        let test = chi2gof(&[16.0, 18.0, 16.0, 14.0, 12.0, 12.0], &[], 0.0).unwrap();
+        assert_eq!(test.statistic, 2.0);
+        assert_eq!(test.df, 5.0);
+        assert!((test.p_value - 0.8491450360846097).abs() < 1e-12);
+        assert_eq!(test.confidence_interval, None);
+
+        // Expected probabilities are rescaled to the observed total
+        let test = chi2gof(&[30.0, 50.0, 20.0], &[0.25, 0.5, 0.25], 0.0).unwrap();
+        assert!((test.statistic - 2.0).abs() < 1e-14);
+        assert!(chi2gof(&[30.0, 50.0], &[0.25, 0.5, 0.25], 0.0).is_err());
+
+        let test = chi2_independence(&[10.0, 20.0, 30.0, 20.0, 20.0, 10.0], 3).unwrap();
+        assert!((test.statistic - 12.527777777777779).abs() < 1e-12);
+        assert_eq!(test.df, 2.0);
+        assert!((test.p_value - 0.0019038276076954964).abs() < 1e-14);
+        assert!(chi2_independence(&[10.0, 20.0, 30.0], 3).is_err());
+    }
+
+    #[test]
+    fn anova1_test() {
+        let groups = vec![
+            vec![6.0, 8.0, 4.0, 5.0, 3.0, 4.0],
+            vec![8.0, 12.0, 9.0, 11.0, 6.0, 8.0],
+            vec![13.0, 9.0, 11.0, 8.0, 7.0, 12.0],
+        ];
+        let test = anova1(&groups).unwrap();
+        assert!((test.statistic - 9.264705882352942).abs() < 1e-12);
+        assert_eq!((test.df, test.df2), (2.0, Some(15.0)));
+        assert!((test.p_value - 0.0023987773293929083).abs() < 1e-14);
+
+        let json = js_anova1(
+            "[[6, 8, 4, 5, 3, 4], [8, 12, 9, 11, 6, 8], [13, 9, 11, 8, 7, 12]]".to_string(),
+        )
+        .unwrap();
+        assert!(json.starts_with("{\"Statistic\":9.26470588235294"));
+        assert!(json.contains("\"Df\":2.0,\"Df2\":15.0,\"PValue\":0.00239877"));
+        assert!(json.ends_with("\"ConfidenceInterval\":null}"));
+        assert!(anova1(&groups[..1]).is_err());
+    }
+
+    #[test]
+    fn prop_ztest_test() {
+        let test = prop_ztest(45.0, 100.0, 0.5, 0.05, Tail::Both).unwrap();
+        assert!((test.statistic + 1.0).abs() < 1e-12);
+        assert_eq!(test.df, f64::INFINITY);
+        assert!((test.p_value - 0.3173105078629141).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower - 0.3524930229100606).abs() < 1e-12);
+        assert!((upper - 0.5475069770899393).abs() < 1e-12);
+
+        let test = prop_ztest2(45.0, 100.0, 60.0, 120.0, 0.05, Tail::Both).unwrap();
+        assert!((test.statistic + 0.7393130939809446).abs() < 1e-12);
+        assert_eq!(test.df, f64::INFINITY);
+        assert!((test.p_value - 0.4597169000968614).abs() < 1e-12);
+        let (lower, upper) = test.confidence_interval.unwrap();
+        assert!((lower + 0.1823278118575131).abs() < 1e-12);
+        assert!((upper - 0.0823278118575131).abs() < 1e-12);
+
+        assert!(prop_ztest(45.5, 100.0, 0.5, 0.05, Tail::Both).is_err());
+        assert!(prop_ztest(45.0, 100.0, 1.0, 0.05, Tail::Both).is_err());
+    }
+
+    //  ------------------------------
+    //  NONPARAMETRIC TESTS
+    //  ------------------------------
+
+    #[test]
+    fn ranks_test() {
+        let (r, ties) = ranks(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0]);
+        assert_eq!(r, vec![4.0, 1.5, 5.0, 1.5, 6.5, 9.0, 3.0, 8.0, 6.5]);
+        assert_eq!(ties, 12.0);
+
+        // Exact distributions add up to one
+        assert!((mann_whitney_distribution(7, 5).iter().sum::<f64>() - 1.0).abs() < 1e-15);
+        assert_eq!(
+            signed_rank_distribution(3),
+            vec![0.125, 0.125, 0.125, 0.25, 0.125, 0.125, 0.125]
+        );
+    }
+
+    #[test]
+    fn ranksum_test() {
+        // Compare with R: wilcox.test(x, y, alternative = "greater")
+        let x = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+        let y = [1.15, 0.88, 0.90, 0.74, 1.21];
+        let test = ranksum(&x, &y, Tail::Right).unwrap();
+        assert_eq!(test.statistic, 35.0);
+        assert!((test.p_value - 0.1272061272061272).abs() < 1e-12);
+
+        let both = ranksum(&x, &y, Tail::Both).unwrap();
+        assert!((both.p_value - 2.0 * test.p_value).abs() < 1e-15);
+        let left = ranksum(&y, &x, Tail::Left).unwrap();
+        assert_eq!(left.statistic, 15.0);
+        assert!((left.p_value - test.p_value).abs() < 1e-15);
+
+        // Ties: normal approximation with continuity correction
+        let test = ranksum(
+            &[1.0, 2.0, 2.0, 3.0, 4.0],
+            &[2.0, 3.0, 5.0, 6.0, 6.0, 7.0],
+            Tail::Both,
+        )
+        .unwrap();
+        assert_eq!(test.statistic, 4.5);
+        assert!((test.p_value - 0.0641466187344044).abs() < 1e-12);
+        assert!(ranksum(&[], &y, Tail::Both).is_err());
+    }
+
+    #[test]
+    // The depression scores of R's wilcox.test example include 3.14
+    #[allow(clippy::approx_constant)]
+    fn signrank_test() {
+        // Compare with R: wilcox.test(x, y, paired = TRUE, alternative = "greater")
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+        let test = signrank_paired(&x, &y, Tail::Right).unwrap();
+        assert_eq!(test.statistic, 40.0);
+        assert!((test.p_value - 0.01953125).abs() < 1e-15);
+        assert!((signrank_paired(&x, &y, Tail::Both).unwrap().p_value - 0.0390625).abs() < 1e-15);
+
+        // Zero differences are dropped
+        let test = signrank(&[1.0, 2.0, 3.0, 5.0], 2.0, Tail::Both).unwrap();
+        assert_eq!(test.statistic, 4.5);
+        assert!(signrank(&[2.0, 2.0], 2.0, Tail::Both).is_err());
+    }
+
+    #[test]
+    fn kruskalwallis_test() {
+        // Compare with R: kruskal.test(list(x, y, z))
+        let groups = vec![
+            vec![2.9, 3.0, 2.5, 2.6, 3.2],
+            vec![3.8, 2.7, 4.0, 2.4],
+            vec![2.8, 3.4, 3.7, 2.2, 2.0],
+        ];
+        let test = kruskalwallis(&groups).unwrap();
+        assert!((test.statistic - 0.7714285714285714).abs() < 1e-12);
+        assert_eq!(test.df, 2.0);
+        assert!((test.p_value - 0.6799647735788936).abs() < 1e-12);
+        assert!(kruskalwallis(&groups[..1]).is_err());
+    }
+
+    #[test]
+    fn kolmogorov_test() {
+        // Critical values of the one-sample statistic at the 5% and 1% levels
+        assert!((1.0 - kolmogorov_cdf(10, 0.40925) - 0.05).abs() < 1e-4);
+        assert!((1.0 - kolmogorov_cdf(10, 0.48893) - 0.01).abs() < 1e-4);
+        assert!((1.0 - kolmogorov_cdf(20, 0.29408) - 0.05).abs() < 1e-4);
+        assert!((1.0 - kolmogorov_cdf(100, 0.13403) - 0.05).abs() < 1e-3);
+        assert!((smirnov_survival(10, 0.36866) - 0.05).abs() < 1e-4);
+
+        // Lattice values d = k/n, where the last term of the sum vanishes
+        assert!((smirnov_survival(11, 2.0 / 11.0) - 0.431492530233194).abs() < 1e-12);
+        let x: Vec<f64> = (1..=11).map(f64::from).collect();
+        let test = kstest(&x, |v| ((v - 2.0) / 11.0).clamp(0.0, 1.0), Tail::Right).unwrap();
+        assert!((test.p_value - 0.4314925302331938).abs() < 1e-12);
+        assert!((kolmogorov_survival(1.3581) - 0.05).abs() < 1e-4);
+        assert!((kolmogorov_survival(0.8) - 0.5441424).abs() < 1e-7);
+
+        // A sample at the quantiles (i - 1/2)/n of the distribution fits it closely
+        let n = 30;
+        let x: Vec<f64> = (0..n)
+            .map(|i| norminv((i as f64 + 0.5) / n as f64, 1.0, 2.0))
+            .collect();
+        let test = kstest(&x, |v| normcdf(v, 1.0, 2.0), Tail::Both).unwrap();
+        assert!((test.statistic - 0.5 / n as f64).abs() < 1e-12);
+        assert_eq!(test.p_value, 1.0);
+
+        let test = kstest(&x, |v| normcdf(v, -1.0, 2.0), Tail::Both).unwrap();
+        assert!(test.p_value < 0.05);
+        let test = kstest(&x, |v| normcdf(v, -1.0, 2.0), Tail::Left).unwrap();
+        assert!(test.p_value < 0.05);
+        let test = kstest(&x, |v| normcdf(v, -1.0, 2.0), Tail::Right).unwrap();
+        assert!(test.p_value > 0.5);
+
+        // The cumulate distribution must return probabilities
+        assert!(kstest(&x, |_| f64::NAN, Tail::Both).is_err());
+        assert!(kstest(&x, |v| v, Tail::Right).is_err());
+    }
+
+    #[test]
+    fn kstest2_test() {
+        // Completely separated samples: 2 of the C(10, 5) orderings are as extreme
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [6.0, 7.0, 8.0, 9.0, 10.0];
+        let test = kstest2(&x, &y, Tail::Both).unwrap();
+        assert_eq!(test.statistic, 1.0);
+        assert!((test.p_value - 2.0 / 252.0).abs() < 1e-15);
+        assert!((kstest2(&x, &y, Tail::Right).unwrap().p_value - 1.0 / 252.0).abs() < 1e-15);
+        assert_eq!(kstest2(&x, &y, Tail::Left).unwrap().statistic, 0.0);
+        assert!((kstest2(&y, &x, Tail::Left).unwrap().p_value - 1.0 / 252.0).abs() < 1e-15);
+
+        // The same sample
+        assert_eq!(kstest2(&x, &x, Tail::Both).unwrap().p_value, 1.0);
+
+        // Large samples use the limiting distribution
+        let x: Vec<f64> = (0..150).map(|i| i as f64).collect();
+        let y: Vec<f64> = (0..100).map(|i| i as f64 * 1.5 + 30.0).collect();
+        let test = kstest2(&x, &y, Tail::Both).unwrap();
+        assert!(test.statistic > 0.1 && test.p_value < 0.05);
+    }
+
+    #[test]
+    fn shapiro_wilk_test() {
+        // Compare with R: shapiro.test(x)
+        let x = [
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0,
+        ];
+        let test = shapiro_wilk(&x).unwrap();
+        assert!((test.statistic - 0.7888146948353875).abs() < 1e-12);
+        assert!((test.p_value - 0.006703814056502999).abs() < 1e-10);
+
+        let x = [
+            2.1, 3.4, 1.9, 5.6, 4.4, 3.8, 2.9, 4.1, 3.3, 4.8, 3.0, 2.5, 3.9, 4.6, 3.6, 2.7, 5.1,
+            3.2, 4.0, 3.5,
+        ];
+        let test = shapiro_wilk(&x).unwrap();
+        assert!((test.statistic - 0.9905496306127013).abs() < 1e-12);
+        assert!((test.p_value - 0.9987273334535767).abs() < 1e-10);
+
+        let test = shapiro_wilk(&[1.0, 2.0, 4.0]).unwrap();
+        assert!((test.statistic - 0.9642857142857146).abs() < 1e-12);
+        assert!((test.p_value - 0.6368868450289714).abs() < 1e-10);
+
+        assert!(shapiro_wilk(&[1.0, 2.0]).is_err());
+        assert!(shapiro_wilk(&[1.0, 1.0, 1.0]).is_err());
     }
 }